@@ -0,0 +1,140 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use loop_unwrap::{unwrap_break, unwrap_continue};
+
+fn inputs() -> Vec<Option<u64>> {
+    (0..10_000)
+        .map(|i| if i % 7 == 0 { None } else { Some(i) })
+        .collect()
+}
+
+fn continue_bare(c: &mut Criterion) {
+    let inputs = inputs();
+    let mut group = c.benchmark_group("continue_bare");
+    group.bench_function("unwrap_continue", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            for x in black_box(&inputs) {
+                sum += unwrap_continue!(*x);
+            }
+            sum
+        })
+    });
+    group.bench_function("match", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            for x in black_box(&inputs) {
+                sum += match *x {
+                    Some(v) => v,
+                    None => continue,
+                };
+            }
+            sum
+        })
+    });
+    group.bench_function("let_else", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            for x in black_box(&inputs) {
+                let Some(v) = *x else { continue };
+                sum += v;
+            }
+            sum
+        })
+    });
+    group.finish();
+}
+
+fn break_bare(c: &mut Criterion) {
+    let mut inputs = inputs();
+    inputs.retain(Option::is_some);
+    inputs.push(None);
+    let mut group = c.benchmark_group("break_bare");
+    group.bench_function("unwrap_break", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            for x in black_box(&inputs) {
+                sum += unwrap_break!(*x);
+            }
+            sum
+        })
+    });
+    group.bench_function("match", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            for x in black_box(&inputs) {
+                sum += match *x {
+                    Some(v) => v,
+                    None => break,
+                };
+            }
+            sum
+        })
+    });
+    group.finish();
+}
+
+fn parse(c: &mut Criterion) {
+    let inputs: Vec<String> = (0..10_000)
+        .map(|i| {
+            if i % 7 == 0 {
+                String::from("x")
+            } else {
+                i.to_string()
+            }
+        })
+        .collect();
+    let mut group = c.benchmark_group("parse");
+    group.bench_function("unwrap_continue", |b| {
+        b.iter(|| {
+            let mut sum = 0u64;
+            for input in black_box(&inputs) {
+                sum += unwrap_continue!(input.parse::<u64>());
+            }
+            sum
+        })
+    });
+    group.bench_function("match", |b| {
+        b.iter(|| {
+            let mut sum = 0u64;
+            for input in black_box(&inputs) {
+                sum += match input.parse::<u64>() {
+                    Ok(v) => v,
+                    Err(_) => continue,
+                };
+            }
+            sum
+        })
+    });
+    group.finish();
+}
+
+fn by_ref(c: &mut Criterion) {
+    let inputs: Vec<Option<[u64; 512]>> = (0..1_000)
+        .map(|i| if i % 7 == 0 { None } else { Some([i; 512]) })
+        .collect();
+    let mut group = c.benchmark_group("by_ref");
+    group.bench_function("unwrap_continue_by_ref", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            for x in black_box(&inputs) {
+                unwrap_continue!(x, by_ref: v);
+                sum += black_box(v)[511];
+            }
+            sum
+        })
+    });
+    group.bench_function("unwrap_continue_move", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            for x in black_box(&inputs) {
+                let v = unwrap_continue!(*x);
+                sum += black_box(v)[511];
+            }
+            sum
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, continue_bare, break_bare, parse, by_ref);
+criterion_main!(benches);
@@ -0,0 +1,141 @@
+//! # loop_unwrap_macros
+//! Procedural macros for [loop_unwrap](https://docs.rs/loop_unwrap). Use them through
+//! `loop_unwrap` with the `derive` or `attribute` feature enabled rather than depending on this
+//! crate directly.
+
+mod rewrite;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::visit_mut::VisitMut;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, ItemFn, Variant};
+
+/// Implements `loop_unwrap::ToOption` for an enum, so it can be passed to the macros.
+/// The variant holding the value is `Some`, every other variant is `None`.
+/// That variant must have exactly one field: it's found automatically if it's the only
+/// one-field variant, or can be marked with `#[to_option(some)]`.
+#[proc_macro_derive(ToOption, attributes(to_option))]
+pub fn derive_to_option(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Makes `?` skip to the next iteration inside the loops of a function, instead of returning.
+/// Every `expr?` in the body of a `loop`, `while` or `for` becomes
+/// `loop_unwrap::unwrap_continue!(expr)`, continuing the innermost loop on an Err or None.
+/// A `?` outside of loops still returns, as do the ones in a `while` condition, in closures and
+/// async blocks, and in items nested in the function. `?` inside other macro calls, like
+/// `println!("{}", x?)`, isn't seen, so it isn't rewritten either.
+#[proc_macro_attribute]
+pub fn loop_unwrap(args: TokenStream, input: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        let args = proc_macro2::TokenStream::from(args);
+        return Error::new_spanned(args, "#[loop_unwrap] doesn't take arguments")
+            .into_compile_error()
+            .into();
+    }
+    let mut function = parse_macro_input!(input as ItemFn);
+    rewrite::Rewriter::default().visit_block_mut(&mut function.block);
+    quote!(#function).into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "ToOption can only be derived for enums",
+            ))
+        }
+    };
+
+    let variant = some_variant(data.variants.iter())?;
+    let ident = &variant.ident;
+    let field = variant.fields.iter().next().unwrap();
+    let value_ty = &field.ty;
+    let pattern = match &variant.fields {
+        Fields::Named(_) => {
+            let name = field.ident.as_ref().unwrap();
+            quote!(Self::#ident { #name: v })
+        }
+        _ => quote!(Self::#ident(v)),
+    };
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let other = if data.variants.len() > 1 {
+        quote!(_ => ::core::option::Option::None,)
+    } else {
+        quote!()
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::loop_unwrap::ToOption<#value_ty> for #name #ty_generics #where_clause {
+            fn to_option(self) -> ::core::option::Option<#value_ty> {
+                match self {
+                    #pattern => ::core::option::Option::Some(v),
+                    #other
+                }
+            }
+        }
+    })
+}
+
+fn some_variant<'a>(
+    variants: impl Iterator<Item = &'a Variant> + Clone,
+) -> syn::Result<&'a Variant> {
+    let mut marked = None;
+    for variant in variants.clone() {
+        for attr in variant
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("to_option"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("some") {
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `some`"))
+                }
+            })?;
+            if marked.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "only one variant can be marked #[to_option(some)]",
+                ));
+            }
+            marked = Some(variant);
+        }
+    }
+
+    let variant = match marked {
+        Some(variant) => variant,
+        None => {
+            let mut single = variants.filter(|v| v.fields.len() == 1);
+            match (single.next(), single.next()) {
+                (Some(variant), None) => variant,
+                (None, _) => {
+                    return Err(Error::new(
+                        Span::call_site(),
+                        "ToOption needs a variant with exactly one field to hold the value",
+                    ))
+                }
+                (Some(_), Some(_)) => return Err(Error::new(
+                    Span::call_site(),
+                    "more than one variant could hold the value, mark it with #[to_option(some)]",
+                )),
+            }
+        }
+    };
+
+    if variant.fields.len() != 1 {
+        return Err(Error::new_spanned(
+            &variant.ident,
+            "the #[to_option(some)] variant must have exactly one field",
+        ));
+    }
+    Ok(variant)
+}
@@ -0,0 +1,49 @@
+use syn::visit_mut::{self, VisitMut};
+use syn::{parse_quote, Expr, Item};
+
+/// Rewrites `expr?` into `unwrap_continue!(expr)` in the bodies of `loop`, `while` and `for`.
+/// A `?` keeps propagating outside of loops, in a `while` condition (where `continue` isn't
+/// allowed), and in closures, async blocks and nested items, which have their own `?`.
+#[derive(Default)]
+pub struct Rewriter {
+    in_loop: bool,
+}
+
+impl Rewriter {
+    fn with_loop(&mut self, in_loop: bool, f: impl FnOnce(&mut Self)) {
+        let outer = std::mem::replace(&mut self.in_loop, in_loop);
+        f(self);
+        self.in_loop = outer;
+    }
+}
+
+impl VisitMut for Rewriter {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Try(try_expr) if self.in_loop => {
+                self.visit_expr_mut(&mut try_expr.expr);
+                let inner = &try_expr.expr;
+                *expr = parse_quote!(::loop_unwrap::unwrap_continue!(#inner));
+            }
+            Expr::Loop(loop_expr) => {
+                self.with_loop(true, |this| this.visit_block_mut(&mut loop_expr.body));
+            }
+            Expr::While(while_expr) => {
+                self.with_loop(false, |this| this.visit_expr_mut(&mut while_expr.cond));
+                self.with_loop(true, |this| this.visit_block_mut(&mut while_expr.body));
+            }
+            Expr::ForLoop(for_expr) => {
+                self.visit_expr_mut(&mut for_expr.expr);
+                self.with_loop(true, |this| this.visit_block_mut(&mut for_expr.body));
+            }
+            Expr::Closure(_) | Expr::Async(_) | Expr::TryBlock(_) => {
+                self.with_loop(false, |this| visit_mut::visit_expr_mut(this, expr));
+            }
+            _ => visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    fn visit_item_mut(&mut self, item: &mut Item) {
+        self.with_loop(false, |this| visit_mut::visit_item_mut(this, item));
+    }
+}
@@ -1,19 +1,245 @@
 //! # loop_unwrap
 //! Provides utility macros for unwrapping during loops.
+//!
+//! # Loop kinds
+//! `continue` and a plain `break` work in `loop`, `while`, `while let` and `for` loops, so
+//! [`unwrap_continue!`], [`unwrap_continue_err!`], [`unwrap_break!`] and the others that don't
+//! break with a value can be used in all of them.
+//! Breaking with a value is only allowed in `loop`, so [`unwrap_break_err!`],
+//! [`unwrap_break_or!`], [`unwrap_break_ok!`], [`break_ok_or_continue!`],
+//! [`unwrap_break_with_msg!`] and `unwrap_break!(x, with: value)` only work there: in other loops
+//! they fail to compile with "`break` with value from a `for` loop" (E0571).
+//! A macro can't tell which kind of loop it's in, so there's no fallback for those.
+//!
+//! ```
+//! # use loop_unwrap::{unwrap_break, unwrap_continue};
+//! let mut stack = vec!["1", "x", "3", "4"];
+//! let mut sum = 0;
+//! while let Some(input) = stack.pop() {
+//!     let n: i32 = unwrap_continue!(input.parse());
+//!     sum += unwrap_break!(n.checked_mul(2));
+//! }
+//! assert_eq!(sum, 16);
+//! ```
+//!
+//! # Nested loops
+//! With a label, the macros break (or continue) any loop they're nested in, not just the
+//! innermost one, so a failure deep inside leaves every level in between at once:
+//! `unwrap_break!(x, 'outer)` is `break 'outer`.
+//! Nothing after the macro runs in the loops it leaves, including the code that follows the
+//! inner loops in the middle ones, so cleanup written there is skipped. Values owned by those
+//! loops are still dropped, innermost first, so cleanup that has to run belongs in a `Drop` impl
+//! (or in the outer loop, after the inner ones).
+//!
+//! ```
+//! # use loop_unwrap::unwrap_break;
+//! let grid = [["1", "2"], ["3", "x"], ["5", "6"]];
+//! let mut trace = Vec::new();
+//! 'rows: for row in &grid {
+//!     for _pass in 0..2 {
+//!         for cell in row {
+//!             let n: i32 = unwrap_break!(cell.parse(), 'rows);
+//!             trace.push(n);
+//!         }
+//!         trace.push(0); // end of a pass, skipped for the pass that failed
+//!     }
+//! }
+//! assert_eq!(trace, vec![1, 2, 0, 1, 2, 0, 3]);
+//! ```
+//!
+//! # Messages and labels
+//! Every macro takes an optional message after its own arguments, printed only when it fails, as
+//! described in [Output](#output). The message can be a format string followed by its
+//! arguments, as with `println!()`, or any [`Message`]: a `String`, a number, or a closure
+//! `|| message` called only on failure. Where the error is at hand, `|e| message` builds the
+//! message from it.
+//!
+//! The macros breaking or continuing a loop also take a label, as in
+//! `unwrap_continue!(x, 'outer, "message")`, to leave that loop instead of the innermost one.
+//! It can come before or after the message, but has to come first when the message is a format
+//! string followed by its arguments.
+//!
+//! # Output
+//! Messages are printed with `println!()`, or `eprintln!()` for the `_eprintln` macros, unless
+//! something else takes them, in this order:
+//! - with the `quiet` feature, they're dropped.
+//! - after `capture_messages()` (`capture` feature), they're collected on the current thread.
+//! - a writer installed with [`set_unwrap_writer`] gets them.
+//! - a destination set with [`configure`] prints them there.
+//! - with the `log` or `tracing` feature, they're logged.
+//!
+//! A prefix or location set with [`configure`] is added to them everywhere but with `quiet`.
+//!
+//! # Unused values
+//! The macros don't warn when the unwrapped value isn't used, as in `unwrap_continue!(x);`.
+//! That's often on purpose: `unwrap_continue!(n > 0);` is a loop guard, and
+//! `unwrap_continue!(word.parse::<i32>(), 'main);` only checks that a word is a number.
+//! Wrapping the value in a `#[must_use]` function would warn for those too, and a warning only
+//! for values other than `()` needs the type to be known where the macro is used, which would
+//! break `let n: i32 = unwrap_continue!(input.parse());`.
+//!
+//! # Async
+//! All the macros work in async code too, as long as the loop they `continue` or `break` is
+//! inside the same async block or function. An awaited value can be passed as
+//! `unwrap_continue!(future.await)`, or with [`unwrap_continue_await!`].
+//!
+//! # no_std
+//! The control-flow-only forms (no message) and [`ToOption`] work without `std`: disable
+//! default features for `#![no_std]` crates. Printing a message needs the `std` feature.
+//!
+//! # Minimum Rust version
+//! Rust 1.65, the first version with `let ... else`, which the forms without a message expand to.
+//!
+//! # Features
+//! - `std` (default): enables the message arms, [`set_unwrap_writer`] and [`configure`].
+//! - `log`: messages are sent to the [`log`](https://docs.rs/log) facade with `log::warn!()`
+//!   (target `loop_unwrap`) instead of being printed to stdout/stderr.
+//! - `tracing`: messages are emitted as [`tracing`](https://docs.rs/tracing) `WARN` events
+//!   (target `loop_unwrap`) instead. Macros that keep the `Err(e)` around, like
+//!   [`unwrap_break_err!`] and [`unwrap_continue_err!`], record it in an `error` field when it
+//!   implements `Debug`. Takes precedence over `log` if both are enabled.
+//! - `anyhow`: the `report:` arms of [`unwrap_continue!`], pushing errors into a
+//!   `Vec<anyhow::Error>`.
+//! - `capture`: `capture_messages()` and `take_messages()`, collecting the messages printed on
+//!   the current thread, to assert on them in tests.
+//! - `derive`: `#[derive(ToOption)]` for using your own enums with the macros, see [`ToOption`].
+//! - `attribute`: `#[loop_unwrap]` on a function, turning the `?`s in its loops into
+//!   [`unwrap_continue!`].
+//! - `metrics`: global counters of how many times the macros skipped or broke out of a loop,
+//!   read with `stats()`. Doesn't need `std`.
+//! - `quiet`: messages aren't printed or sent anywhere, not even to a writer installed with
+//!   [`set_unwrap_writer`], while the macros still skip and break the same way. Takes
+//!   precedence over `log` and `tracing`. Useful to keep test output clean. Messages passed to a
+//!   `via:` callback are still passed to it.
+//! - `panic-in-debug`: in builds with debug assertions (the default for `cargo build` and
+//!   `cargo test`), [`unwrap_continue!`] panics instead of calling `continue`, with the message
+//!   if provided, so unexpected failures are caught during development. Release builds still
+//!   skip. Only the forms without a message and the ones printing one are affected: `via:`,
+//!   `collect:`, `count:`... handle the failure on purpose, so they keep calling `continue`.
+//!   The profile checked is the one of the crate calling the macro. As Cargo features are
+//!   additive, one crate enabling it makes the macros panic in every crate of the build,
+//!   including dependencies relying on them to skip, so only enable it from a binary or for
+//!   your own tests. It's left out of `--all-features` test runs, as the examples skip on
+//!   purpose: test it on its own with `cargo test --features panic-in-debug --test
+//!   panic_in_debug`.
+//!
+//! ```
+//! # use loop_unwrap::unwrap_continue_err;
+//! for input in &["1", "two"] {
+//!     let _n = unwrap_continue_err!(input.parse::<i32>(), "Couldn't parse {:?}", input);
+//!     // with `tracing` enabled, this is a WARN event with
+//!     // message = "Couldn't parse \"two\"" and error = ParseIntError { kind: InvalidDigit }
+//! }
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "std")]
+mod output;
+pub mod prelude;
+
+/// The helpers of `unwrap_continue_classified!` work without `std`, the others come from
+/// `output`.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "std")]
+    pub use crate::output::__private::*;
+
+    /// Lets `unwrap_continue_classified!` ask errors implementing [`SkipError`](crate::SkipError)
+    /// whether to skip, and skip every other error: `(&Classified(&e)).is_skip()` picks
+    /// `ViaSkipError` if it applies, else `SkipByDefault`.
+    pub struct Classified<'a, T>(pub &'a T);
+
+    pub trait ViaSkipError {
+        fn is_skip(&self) -> bool;
+    }
+
+    impl<T: crate::SkipError> ViaSkipError for Classified<'_, T> {
+        fn is_skip(&self) -> bool {
+            self.0.is_skip()
+        }
+    }
+
+    pub trait SkipByDefault {
+        fn is_skip(&self) -> bool {
+            true
+        }
+    }
+
+    impl<T> SkipByDefault for &Classified<'_, T> {}
+}
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub use output::{
+    __loop_unwrap_emit, __loop_unwrap_emit_default, __loop_unwrap_emit_error,
+    __loop_unwrap_emit_skip, __loop_unwrap_emit_stderr, __loop_unwrap_flush, __loop_unwrap_via,
+};
+#[cfg(feature = "capture")]
+pub use output::{capture_messages, take_messages};
+#[cfg(feature = "std")]
+pub use output::{
+    configure, set_default_message, set_unwrap_writer, take_default_message, take_unwrap_writer,
+    Destination, UnwrapConfig,
+};
+
+#[cfg(feature = "metrics")]
+pub use metrics::{stats, UnwrapStats, BREAKS, CONTINUES, ERR_BREAKS};
+
+#[cfg(feature = "attribute")]
+pub use loop_unwrap_macros::loop_unwrap;
+#[cfg(feature = "derive")]
+pub use loop_unwrap_macros::ToOption;
+
+/// The message printed by `unwrap_continue!(x, msg: default)`, unless replaced with
+/// [`set_default_message`].
+pub const DEFAULT_SKIP_MESSAGE: &str = "loop_unwrap: skipped an iteration on an Err or None";
+
+/// The longest sleep of [`unwrap_retry_backoff!`] when it isn't given a `cap:`.
+#[cfg(feature = "std")]
+pub const DEFAULT_BACKOFF_CAP: std::time::Duration = std::time::Duration::from_secs(60);
 
 /// Works like `.unwrap`, if it's an Err or None, it calls `continue` on the loop.
-/// Prints an error message with `println!()` if provided.
-/// Loop Label can be provided in any order besides the Result/Option being the first argument.
-/// If loop label is proivded, the specified loop will be continued.
+/// Takes an optional [message and label](crate#messages-and-labels), printed when skipping as
+/// described in [Output](crate#output). The macro is an expression, so its value can be
+/// destructured with a `let` pattern, as in `let (a, b) = unwrap_continue!(pair);`.
+///
+/// Besides a message, it takes these forms, each shown in the examples below:
+/// - `|e| message`: a message built from the error.
+/// - `"prefix", |e| expression`: prints the prefix, then runs the expression with the error.
+/// - `else |e| { ... }`: runs the block with the error instead of printing.
+/// - `on_err: { ... }`: runs the block before continuing.
+/// - `skip_if: |e| predicate`: only skips the errors the predicate accepts.
+/// - `via: sink, message`: passes the message to a callback instead of printing it.
+/// - `buf: writer, message`: writes the message into a [`core::fmt::Write`].
+/// - `collect: errors`: pushes the error into a collection.
+/// - `report: reports` (`anyhow` feature): pushes the error into a `Vec<anyhow::Error>`.
+/// - `const`: works in a `const fn`.
+/// - `by_ref: name`: binds `name` to a reference to the value.
+/// - `msg: default`: prints the same default message everywhere.
+/// - `tag: "NET", message`: prints the message as `[NET] message`.
+/// - `count: attempts, message`: adds the attempt number to the message.
+/// - `escalate: fails, 'outer, |n| predicate`: breaks `'outer` once enough failures add up.
+///
+/// A label missing its leading quote, as in `unwrap_continue!(x, main, "message")`, is reported
+/// with an error asking for `'main`, here and in [`unwrap_break!`], [`unwrap_break_err!`] and
+/// [`unwrap_continue_err!`]. Only when it's followed by other arguments or comes after a message
+/// that isn't a string literal, though: a macro can't tell `main` from a variable, so
+/// `unwrap_continue!(x, main)` prints the variable `main` as the message, and
+/// `unwrap_continue!(x, "message", main)` takes it as a format argument, failing with
+/// "argument never used".
 /// # Examples
-/// ```
+/// ```no_run
+/// # use loop_unwrap::unwrap_continue;
 /// loop {
 ///         let input = "Not a number";
 ///         let parsed_input: i32 = unwrap_continue!(input.parse()); //parse returns Err for this input
 ///         break; //<-- never reached, since `continue` is called.
 ///     }
 /// ```
-/// ```
+/// ```no_run
+/// # use loop_unwrap::unwrap_continue;
 /// loop {
 ///         let input = "Not a number";
 ///         let parsed_input: i32 = unwrap_continue!(input.parse(), "Please Enter a Number!");
@@ -21,13 +247,15 @@
 ///         break;
 ///     }
 /// ```
-/// ```
+/// ```no_run
+/// # use loop_unwrap::unwrap_continue;
 /// loop {
 ///         let some_value: i32 = unwrap_continue!(Some(32), "Please Enter a Number!");
 ///         assert_eq!(some_value, 32_i32)
 ///     }
 /// ```
-/// ```
+/// ```no_run
+/// # use loop_unwrap::unwrap_continue;
 /// 'main: loop {
 ///         loop {
 ///             let n =
@@ -37,222 +265,5690 @@
 ///         break; //<-- this line won't be reached, since 'main will be continued infinitely
 ///     }
 /// ```
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// let mut sum = 0;
+/// for input in &["1", "two", "3"] {
+///     let n: i32 = unwrap_continue!(input.parse(), "Couldn't parse {:?}, skipping", input);
+///     // "Couldn't parse "two", skipping" is printed in console with a `println!()`
+///     sum += n;
+/// }
+/// assert_eq!(sum, 4);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// let mut rows = 0;
+/// 'main: for line in &["1 2", "3 x"] {
+///     for word in line.split(' ') {
+///         unwrap_continue!(word.parse::<i32>(), 'main, "Bad word {:?} in line {:?}", word, line);
+///     }
+///     rows += 1;
+/// }
+/// assert_eq!(rows, 1);
+/// ```
+/// The message is only evaluated when `continue` is called. It can be anything implementing
+/// `Display` or [`Message`], like a closure `|| message`, which is only called then too, a `String`
+/// variable, a `&String`, a `format!()` call or a number.
+/// Any other type fails to compile, saying it implements neither trait; format it with `{:?}` for
+/// types that only implement `Debug`.
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// use std::cell::Cell;
+///
+/// let built = Cell::new(0);
+/// let expensive_message = || {
+///     built.set(built.get() + 1);
+///     String::from("Couldn't parse")
+/// };
+/// for input in &["1", "two", "3"] {
+///     let _n: i32 = unwrap_continue!(input.parse(), || expensive_message());
+/// }
+/// assert_eq!(built.get(), 1); // only built for "two"
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// let message = String::from("Couldn't parse");
+/// let code = 3;
+/// for input in &["1", "two"] {
+///     let _a: i32 = unwrap_continue!(input.parse(), message.clone());
+///     let _b: i32 = unwrap_continue!(input.parse(), &message);
+///     let _c: i32 = unwrap_continue!(input.parse(), format!("{} {:?}", message, input));
+///     let _d: i32 = unwrap_continue!(input.parse(), code);
+/// }
+/// ```
+/// `|e| message` gets the error value (`()` for an Option) bound to `e` before the message is
+/// printed. The label can go before or after it.
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// let mut sum = 0;
+/// for input in &["1", "two", "3"] {
+///     sum += unwrap_continue!(input.parse::<i32>(), |e| format!("got {}", e));
+///     // "got invalid digit found in string" is printed in console with a `println!()`
+/// }
+/// assert_eq!(sum, 4);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// let mut rows = 0;
+/// 'main: for line in &["1 2", "3 x"] {
+///     for word in line.split(' ') {
+///         unwrap_continue!(word.parse::<i32>(), |e| format!("{:?}: {}", word, e), 'main);
+///     }
+///     rows += 1;
+/// }
+/// 'other: for line in &["x"] {
+///     for word in line.split(' ') {
+///         unwrap_continue!(word.parse::<i32>(), 'other, |e| format!("{:?}: {}", word, e));
+///     }
+///     rows += 1;
+/// }
+/// assert_eq!(rows, 1);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// fn parse_pair(line: &str) -> Result<(i32, i32), String> {
+///     let (a, b) = line.split_once(',').ok_or_else(|| format!("no comma in {:?}", line))?;
+///     Ok((a.parse().map_err(|_| "bad a")?, b.parse().map_err(|_| "bad b")?))
+/// }
+///
+/// let mut sums = Vec::new();
+/// for line in &["1,2", "3", "4,x", "5,6"] {
+///     let (a, b) = unwrap_continue!(parse_pair(line), |e| e);
+///     sums.push(a + b);
+/// }
+/// assert_eq!(sums, vec![3, 11]);
+/// ```
+/// `"prefix", |e| expression` prints the prefix, then evaluates the expression with the error
+/// bound to `e`, e.g. to print the details of the error after a fixed message.
+/// ```
+/// # use loop_unwrap::{set_unwrap_writer, unwrap_continue};
+/// # use std::io::{self, Write};
+/// # use std::sync::{Arc, Mutex};
+/// # #[derive(Clone, Default)]
+/// # struct Captured(Arc<Mutex<Vec<u8>>>);
+/// # impl Write for Captured {
+/// #     fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.lock().unwrap().write(buf) }
+/// #     fn flush(&mut self) -> io::Result<()> { Ok(()) }
+/// # }
+/// # let captured = Captured::default();
+/// # set_unwrap_writer(Box::new(captured.clone()));
+/// let mut details = Vec::new();
+/// for input in &["1", "two"] {
+///     let _n: i32 = unwrap_continue!(input.parse(), "parse failed:", |e| {
+///         details.push(e.to_string()) // or eprintln!("{}", e)
+///     });
+/// }
+/// // `captured` is a writer installed with `set_unwrap_writer`
+/// # #[cfg(not(feature = "quiet"))]
+/// assert_eq!(&*captured.0.lock().unwrap(), b"parse failed:\n");
+/// assert_eq!(details, vec!["invalid digit found in string"]);
+/// ```
+/// `else |e| { ... }` runs the block with the error bound to `e` instead of printing, and
+/// `continue` is called right after it.
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// let mut sum = 0;
+/// let mut errors = Vec::new();
+/// for input in &["1", "two", "3", "four"] {
+///     sum += unwrap_continue!(input.parse::<i32>(), else |e| {
+///         errors.push(e);
+///     });
+/// }
+/// assert_eq!(sum, 4);
+/// assert_eq!(errors.len(), 2);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// let mut bad_lines = 0;
+/// 'main: for line in &["1 2", "3 x", "y 6"] {
+///     for word in line.split(' ') {
+///         unwrap_continue!(word.parse::<i32>(), 'main, else |_| { bad_lines += 1; });
+///     }
+/// }
+/// assert_eq!(bad_lines, 2);
+/// ```
+/// `on_err: { ... }` runs the block on every Err or None right before `continue` is called, like
+/// `else` but without the error. That's also the place to clean up what the iteration acquired
+/// before failing, since the block never runs when unwrapping succeeds, or to back off before
+/// trying again.
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// struct Lease {
+///     released: bool,
+/// }
+///
+/// let mut leases = Vec::new();
+/// for input in &["1", "x", "3", "y"] {
+///     leases.push(Lease { released: false });
+///     let lease = leases.last_mut().unwrap();
+///     let _n: i32 = unwrap_continue!(input.parse(), on_err: {
+///         lease.released = true;
+///     });
+/// }
+/// let released: Vec<_> = leases.iter().map(|lease| lease.released).collect();
+/// assert_eq!(released, vec![false, true, false, true]);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let mut responses = vec![None, None, Some("ok")].into_iter();
+/// let mut failures = 0;
+/// let response = loop {
+///     break unwrap_continue!(responses.next().unwrap(), on_err: {
+///         failures += 1;
+///         thread::sleep(Duration::from_millis(1)); // back off before trying again
+///     });
+/// };
+/// assert_eq!(response, "ok");
+/// assert_eq!(failures, 2);
+/// ```
+/// With `skip_if: |e| predicate`, only errors the predicate returns true for (it gets `&E`, or
+/// `&()` for an Option) call `continue`; any other error breaks the loop with `Err(e)`, so it has
+/// to be a `loop`. The label, if provided, is used for both.
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// #[derive(Debug, PartialEq)]
+/// enum FetchError {
+///     Timeout,
+///     NotFound,
+/// }
+///
+/// impl FetchError {
+///     fn is_transient(&self) -> bool {
+///         *self == FetchError::Timeout
+///     }
+/// }
+///
+/// let mut responses = vec![Err(FetchError::Timeout), Ok(1), Err(FetchError::NotFound), Ok(2)];
+/// responses.reverse();
+/// let mut fetched = Vec::new();
+/// let outcome: Result<(), FetchError> = loop {
+///     let response = responses.pop().unwrap();
+///     fetched.push(unwrap_continue!(response, skip_if: |e| e.is_transient()));
+/// };
+/// assert_eq!(fetched, vec![1]); // the timeout was skipped, NotFound stopped the loop
+/// assert_eq!(outcome, Err(FetchError::NotFound));
+/// ```
+/// With `via: sink, message`, the message is passed to `sink`, a `&mut dyn FnMut(&str)` (like
+/// `&mut closure`), instead of being printed, e.g. to collect the messages in a test.
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// let mut messages: Vec<String> = Vec::new();
+/// let mut collect = |message: &str| messages.push(message.to_string());
+/// let sink: &mut dyn FnMut(&str) = &mut collect;
+/// let mut sum = 0;
+/// 'main: for line in &["1 2", "x 3", "4"] {
+///     for word in line.split(' ') {
+///         sum += unwrap_continue!(word.parse::<i32>(), 'main, via: sink, "Bad line {:?}", line);
+///     }
+/// }
+/// for input in &["5", ""] {
+///     sum += unwrap_continue!(input.parse::<i32>(), via: &mut collect, "Empty input");
+/// }
+/// assert_eq!(sum, 12);
+/// assert_eq!(messages, vec!["Bad line \"x 3\"", "Empty input"]);
+/// ```
+/// With `buf: writer, message`, the message is written into `writer`, a `&mut` to anything
+/// implementing [`core::fmt::Write`], as is, without a newline. That works without `std`, and
+/// without allocating when the writer doesn't, like a fixed-size buffer on the stack. Errors from
+/// the writer, such as a buffer running out of room, are ignored.
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// let mut log = String::new();
+/// for input in &["1", "two", "3", "four"] {
+///     let _n: i32 = unwrap_continue!(input.parse(), buf: &mut log, "bad input {:?}; ", input);
+/// }
+/// assert_eq!(log, "bad input \"two\"; bad input \"four\"; ");
+/// ```
+/// With `collect: errors`, the error is pushed into `errors` (any collection implementing
+/// `Extend`, like a `Vec`) instead, so it can be looked at after the loop.
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// use std::num::ParseIntError;
+///
+/// let mut sum = 0;
+/// let mut errors: Vec<ParseIntError> = Vec::new();
+/// for input in &["1", "two", "3", ""] {
+///     sum += unwrap_continue!(input.parse::<i32>(), collect: errors);
+/// }
+/// assert_eq!(sum, 4);
+/// assert_eq!(
+///     errors.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+///     vec!["invalid digit found in string", "cannot parse integer from empty string"]
+/// );
+/// ```
+/// With the `anyhow` feature, `report: reports` does the same for a Result, converting the error
+/// into an [`anyhow::Error`](https://docs.rs/anyhow) first, so errors of different types can be
+/// pushed into the same `Vec<anyhow::Error>`.
+/// ```
+/// # #[cfg(feature = "anyhow")] {
+/// # use loop_unwrap::unwrap_continue;
+/// let mut reports: Vec<anyhow::Error> = Vec::new();
+/// for input in &["1", "two"] {
+///     let _n: i32 = unwrap_continue!(input.parse(), report: reports);
+/// }
+/// for input in &["1.5", "x"] {
+///     let _n: f64 = unwrap_continue!(input.parse(), report: reports);
+/// }
+/// assert_eq!(reports.len(), 2);
+/// # }
+/// ```
+/// With `const` after an Option (and the label if any), as in `unwrap_continue!(x, const)` or
+/// `unwrap_continue!(x, 'outer, const)`, it works in a `const fn` too. The other forms can't: they
+/// go through [`ToOption`], and trait methods can't be called in const contexts yet, so these take
+/// an Option only and match on it directly. A message can't be printed there either, and
+/// `panic-in-debug` doesn't apply.
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// const fn sum_present(values: &[Option<u32>]) -> u32 {
+///     let mut sum = 0;
+///     let mut i = 0;
+///     while i < values.len() {
+///         i += 1;
+///         sum += unwrap_continue!(values[i - 1], const);
+///     }
+///     sum
+/// }
+/// const SUM: u32 = sum_present(&[Some(1), None, Some(3)]);
+/// assert_eq!(SUM, 4);
+/// ```
+/// With `by_ref: name`, as in `unwrap_continue!(x, by_ref: frame);`, the macro is a statement
+/// instead: it borrows `x` in a `let` of its own and binds `name` to a reference to the value
+/// inside, through [`AsOption`]. Nothing is moved or cloned, which matters when the value is large
+/// and the loop only reads it. `x` can be a place like `items[i]`, a reference like the items of
+/// `for item in &items`, or a temporary like `f()`, which is kept alive until the end of the
+/// block.
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// let frames: Vec<Result<Vec<u8>, String>> = vec![Ok(vec![0; 4096]), Err("dropped".into())];
+/// let mut total = 0;
+/// for frame in &frames {
+///     unwrap_continue!(frame, by_ref: pixels);
+///     total += pixels.len();
+/// }
+/// assert_eq!(total, 4096);
+/// ```
+/// With `msg: default` in place of the message, the message set with [`set_default_message`] is
+/// printed, or [`DEFAULT_SKIP_MESSAGE`] if none is, for the same wording everywhere without
+/// repeating it at every call site.
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// for input in &["1", "two"] {
+///     let _n: i32 = unwrap_continue!(input.parse(), msg: default);
+///     // "loop_unwrap: skipped an iteration on an Err or None" is printed for "two"
+/// }
+/// ```
+/// With `tag: "NET", message`, the message is printed as `[NET] message`, to tell apart the
+/// messages of different parts of a program. The message can be a format string here too.
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// for port in &["80", "http"] {
+///     let _port: u16 = unwrap_continue!(port.parse(), tag: "NET", "bad port {:?}", port);
+///     // "[NET] bad port "http"" is printed
+/// }
+/// ```
+/// With `count: attempts, message`, the message is followed by ` (attempt N)`, where N is read from
+/// `attempts`. `attempts` is incremented every time the macro runs, before unwrapping, so starting
+/// it at 0 outside the loop gives the 1-based number of the current attempt.
+/// ```
+/// # use loop_unwrap::{set_unwrap_writer, unwrap_continue};
+/// # use std::io::{self, Write};
+/// # use std::sync::{Arc, Mutex};
+/// # #[derive(Clone, Default)]
+/// # struct Captured(Arc<Mutex<Vec<u8>>>);
+/// # impl Write for Captured {
+/// #     fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.lock().unwrap().write(buf) }
+/// #     fn flush(&mut self) -> io::Result<()> { Ok(()) }
+/// # }
+/// # let captured = Captured::default();
+/// # set_unwrap_writer(Box::new(captured.clone()));
+/// let mut attempts = 0;
+/// for input in &["x", "2", "y"] {
+///     let n: i32 = unwrap_continue!(input.parse(), count: attempts, "Couldn't parse");
+///     println!("{}", n);
+/// }
+/// assert_eq!(attempts, 3);
+/// // `captured` is a writer installed with `set_unwrap_writer`
+/// let printed = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+/// # #[cfg(not(feature = "quiet"))]
+/// assert_eq!(printed, "Couldn't parse (attempt 1)\nCouldn't parse (attempt 3)\n");
+/// ```
+/// With `escalate: fails, 'outer, |n| predicate`, `fails` is incremented on every Err or None, then
+/// the predicate gets `&fails`: if it returns true, the `'outer` loop is broken, otherwise the loop
+/// is continued as usual (or the label given before `escalate:`). `fails` isn't reset by the macro,
+/// so set it back to 0 where the count should start over.
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// let batches = [vec!["1", "x", "2"], vec!["y", "3"], vec!["z", "4"], vec!["5"]];
+/// let mut fails = 0;
+/// let mut sum = 0;
+/// 'batches: for batch in &batches {
+///     'inputs: for input in batch {
+///         for _ in 0..2 {
+///             let n: i32 =
+///                 unwrap_continue!(input.parse(), 'inputs, escalate: fails, 'batches, |n| *n >= 3);
+///             sum += n;
+///         }
+///     }
+/// }
+/// assert_eq!(fails, 3); // the third failure, on "z", gave up on the batches left
+/// assert_eq!(sum, 12); // 1, 2 and 3, twice each
+///
+/// let mut fails = 0;
+/// 'retries: loop {
+///     for input in &["a", "b", "c", "d"] {
+///         let _n: i32 = unwrap_continue!(input.parse(), escalate: fails, 'retries, |n| *n >= 3);
+///     }
+/// }
+/// assert_eq!(fails, 3);
+/// ```
 #[macro_export]
 macro_rules! unwrap_continue {
-    ($x:expr) => {
+    ($x:expr) => {{
+        let Some(v) = ::loop_unwrap::ToOption::to_option($x) else {
+            ::loop_unwrap::__loop_unwrap_debug_panic(cfg!(debug_assertions));
+            ::loop_unwrap::__loop_unwrap_count_continue();
+            continue;
+        };
+        v
+    }};
+    ($x:expr, $label:lifetime) => {{
+        let Some(v) = ::loop_unwrap::ToOption::to_option($x) else {
+            ::loop_unwrap::__loop_unwrap_debug_panic(cfg!(debug_assertions));
+            ::loop_unwrap::__loop_unwrap_count_continue();
+            continue $label;
+        };
+        v
+    }};
+    ($x:expr, const) => {{
+        let Some(v) = $x else {
+            continue;
+        };
+        v
+    }};
+    ($x:expr, $label:lifetime, const) => {{
+        let Some(v) = $x else {
+            continue $label;
+        };
+        v
+    }};
+    ($x:expr, const, $label:lifetime) => {{
+        let Some(v) = $x else {
+            continue $label;
+        };
+        v
+    }};
+    ($x:expr, by_ref: $name:ident) => {
+        let value = &$x;
+        let $name = match {
+            #[allow(unused_imports)]
+            use ::loop_unwrap::AsOption as _;
+            value.as_option()
+        } {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_debug_panic(cfg!(debug_assertions));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        };
+    };
+    ($x:expr, $label:lifetime, by_ref: $name:ident) => {
+        let value = &$x;
+        let $name = match {
+            #[allow(unused_imports)]
+            use ::loop_unwrap::AsOption as _;
+            value.as_option()
+        } {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_debug_panic(cfg!(debug_assertions));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        };
+    };
+    ($x:expr, by_ref: $name:ident, $label:lifetime) => {
+        let value = &$x;
+        let $name = match {
+            #[allow(unused_imports)]
+            use ::loop_unwrap::AsOption as _;
+            value.as_option()
+        } {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_debug_panic(cfg!(debug_assertions));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        };
+    };
+    ($x:expr, msg: default) => {
         match ::loop_unwrap::ToOption::to_option($x) {
             Some(v) => v,
             None => {
+                ::loop_unwrap::__loop_unwrap_emit_default(cfg!(debug_assertions));
+                ::loop_unwrap::__loop_unwrap_count_continue();
                 continue;
             }
         }
     };
-    ($x:expr, $label:lifetime) => {
+    ($x:expr, $label:lifetime, msg: default) => {
         match ::loop_unwrap::ToOption::to_option($x) {
             Some(v) => v,
             None => {
+                ::loop_unwrap::__loop_unwrap_emit_default(cfg!(debug_assertions));
+                ::loop_unwrap::__loop_unwrap_count_continue();
                 continue $label;
             }
         }
     };
-    ($x:expr, $label:lifetime, $err_msg:expr) => {
+    ($x:expr, msg: default, $label:lifetime) => {
         match ::loop_unwrap::ToOption::to_option($x) {
             Some(v) => v,
             None => {
-                println!("{}", $err_msg);
+                ::loop_unwrap::__loop_unwrap_emit_default(cfg!(debug_assertions));
+                ::loop_unwrap::__loop_unwrap_count_continue();
                 continue $label;
             }
         }
     };
-    ($x:expr, $err_msg:expr) => {
+    ($x:expr, via: $sink:expr, $err_msg:expr) => {
         match ::loop_unwrap::ToOption::to_option($x) {
             Some(v) => v,
             None => {
-                println!("{}", $err_msg);
+                ::loop_unwrap::__loop_unwrap_via($sink, format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
                 continue;
             }
         }
     };
-    ($x:expr, $err_msg:expr, $label:lifetime) => {
+    ($x:expr, $label:lifetime, via: $sink:expr, $err_msg:expr) => {
         match ::loop_unwrap::ToOption::to_option($x) {
             Some(v) => v,
             None => {
-                println!("{}", $err_msg);
+                ::loop_unwrap::__loop_unwrap_via($sink, format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
                 continue $label;
             }
         }
     };
-
-
-
-}
-
-/// Works like `.unwrap`, if it's an Err or None, it calls `break` on the loop.
-/// Prints an error message with `println!()` if provided.
-/// Loop Label can be provided in any order besides the Result/Option being the first argument.
-/// If loop label is proivded, the specified loop will be break;-ed.
-/// # Examples
-/// ```
-/// loop {
-///         let input = "Not a number";
-///         let parsed_input: i32 = unwrap_break!(input.parse()); //parse returns Err for this input
-///     }
-/// println!("This line will be reached.");
-/// ```
-/// ```
-/// loop {
-///         let input = "Not a number";
-///         let parsed_input: i32 = unwrap_break!(input.parse(), "Please Enter a Number!");
-///         // "Please Enter a Number!" is printed in console with a `println!()`
-///         //loop breaks
-///     }
-/// ```
-/// ```
-/// loop {
-///         let some_value: i32 = unwrap_break!(Some(32), "Please Enter a Number!");
-///         assert_eq!(some_value, 32_i32)
-///         //no breakage here.
-///     }
-/// ```
-/// ```
-/// 'main: loop {
-///        loop {
-///            let n = unwrap_break!("t".parse::<i32>(), "Couldn't parse, exiting main loop", 'main);
-///            break; //<-- this line won't be reached.
-///        }
-///        println!("This line will never be reached, because 'main breaks.");
-///    }
-/// ```   
-#[macro_export]
-macro_rules! unwrap_break {
-    ($x:expr) => {
+    ($x:expr, $label:lifetime, via: $sink:expr, $fmt:literal, $($args:expr),+ $(,)?) => {
         match ::loop_unwrap::ToOption::to_option($x) {
             Some(v) => v,
             None => {
-                break;
+                ::loop_unwrap::__loop_unwrap_via($sink, format_args!($fmt, $($args),+));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
             }
         }
     };
-    ($x:expr, $label:lifetime) => {
+    ($x:expr, via: $sink:expr, $err_msg:expr, $label:lifetime) => {
         match ::loop_unwrap::ToOption::to_option($x) {
             Some(v) => v,
             None => {
-                break $label;
+                ::loop_unwrap::__loop_unwrap_via($sink, format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
             }
         }
     };
-    ($x:expr, $label:lifetime, $err_msg:expr) => {
+    ($x:expr, via: $sink:expr, $fmt:literal, $($args:expr),+ $(,)?) => {
         match ::loop_unwrap::ToOption::to_option($x) {
             Some(v) => v,
             None => {
-                println!("{}", $err_msg);
-                break $label;
+                ::loop_unwrap::__loop_unwrap_via($sink, format_args!($fmt, $($args),+));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
             }
         }
     };
-    ($x:expr, $err_msg:expr) => {
+    ($x:expr, buf: $buf:expr, $err_msg:expr, $label:lifetime) => {
         match ::loop_unwrap::ToOption::to_option($x) {
             Some(v) => v,
             None => {
-                println!("{}", $err_msg);
-                break;
+                let _ = ::core::fmt::Write::write_fmt($buf, format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
             }
         }
     };
-    ($x:expr, $err_msg:expr, $label:lifetime) => {
+    ($x:expr, buf: $buf:expr, $fmt:literal, $($args:expr),+ $(,)?) => {
         match ::loop_unwrap::ToOption::to_option($x) {
             Some(v) => v,
             None => {
-                println!("{}", $err_msg);
-                break $label;
+                let _ = ::core::fmt::Write::write_fmt($buf, format_args!($fmt, $($args),+));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
             }
         }
     };
-}
-
-/// Works only on Result enum. If the value is Err(e), breaks the loop returning Err(e).
-/// Otherwise, it unwraps and the code continues.
-/// Supports loop labels.
-/// # Examples
-/// ```
-/// let value = loop {
-///        let s = "not a number";
-///        let n = unwrap_break_err!(s.parse::<i32>(), "Couldn't parse number."); // breaks with error value
-///        break Ok(n + 1); //<-- this line will never be reached since the macro breaks
-///    };
-///    assert_eq!(true, value.is_err());
-/// ```
-/// ```
-/// let result = 'main: loop {
-///         loop {
-///             let n = unwrap_break_err!("t".parse::<i32>(), 'main);
-///             break 'main Ok(100);
-///         }
-///     };
-///     assert_eq!(result.is_err(), true);
-/// ```    
-#[macro_export]
-macro_rules! unwrap_break_err {
-    ($x:expr) => {
-        match $x {
+    ($x:expr, buf: $buf:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                let _ = ::core::fmt::Write::write_fmt($buf, format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, buf: $buf:expr, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                let _ = ::core::fmt::Write::write_fmt($buf, format_args!($fmt, $($args),+));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, buf: $buf:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                let _ = ::core::fmt::Write::write_fmt($buf, format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, collect: $errors:expr) => {
+        match ::loop_unwrap::ToResultLike::into_parts($x) {
             Ok(v) => v,
             Err(e) => {
-                break Err(e);
+                $errors.extend(::core::iter::once(e));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
             }
         }
     };
-    ($x:expr, $label:lifetime) => {
-        match $x {
+    ($x:expr, $label:lifetime, collect: $errors:expr) => {
+        match ::loop_unwrap::ToResultLike::into_parts($x) {
             Ok(v) => v,
             Err(e) => {
-                break $label Err(e);
+                $errors.extend(::core::iter::once(e));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
             }
         }
     };
-    ($x:expr, $label:lifetime, $err_msg:expr) => {
+    ($x:expr, collect: $errors:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToResultLike::into_parts($x) {
+            Ok(v) => v,
+            Err(e) => {
+                $errors.extend(::core::iter::once(e));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, report: $reports:expr) => {
         match $x {
             Ok(v) => v,
             Err(e) => {
-                println!("{}", $err_msg);
-                break $label Err(e);
+                $reports.extend(::core::iter::once(::loop_unwrap::__loop_unwrap_report(e)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
             }
         }
     };
-    ($x:expr, $err_msg:expr) => {
+    ($x:expr, $label:lifetime, report: $reports:expr) => {
         match $x {
             Ok(v) => v,
             Err(e) => {
-                println!("{}", $err_msg);
-                break Err(e);
+                $reports.extend(::core::iter::once(::loop_unwrap::__loop_unwrap_report(e)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
             }
         }
     };
-    ($x:expr, $err_msg:expr, $label:lifetime) => {
+    ($x:expr, report: $reports:expr, $label:lifetime) => {
         match $x {
             Ok(v) => v,
             Err(e) => {
-                println!("{}", $err_msg);
-                break $label Err(e);
+                $reports.extend(::core::iter::once(::loop_unwrap::__loop_unwrap_report(e)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
             }
         }
     };
-}
-
-pub trait ToOption<T> {
-    fn to_option(self) -> Option<T>;
-}
-
-impl<T> ToOption<T> for Option<T> {
-    fn to_option(self) -> Option<T> {
-        self
-    }
-}
-
-impl<T, U> ToOption<T> for Result<T, U> {
-    fn to_option(self) -> Option<T> {
-        match self {
-            Ok(v) => Some(v),
-            Err(_) => None,
+    ($x:expr, tag: $tag:expr, $err_msg:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit_skip(
+                    cfg!(debug_assertions),
+                    format_args!("[{}] {}", $tag, ::loop_unwrap::__loop_unwrap_message!($err_msg)),
+                );
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, tag: $tag:expr, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit_skip(
+                    cfg!(debug_assertions),
+                    format_args!("[{}] {}", $tag, format_args!($fmt, $($args),+)),
+                );
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
         }
+    };
+    ($x:expr, tag: $tag:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit_skip(
+                    cfg!(debug_assertions),
+                    format_args!("[{}] {}", $tag, ::loop_unwrap::__loop_unwrap_message!($err_msg)),
+                );
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, tag: $tag:expr, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit_skip(
+                    cfg!(debug_assertions),
+                    format_args!("[{}] {}", $tag, format_args!($fmt, $($args),+)),
+                );
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, tag: $tag:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit_skip(
+                    cfg!(debug_assertions),
+                    format_args!("[{}] {}", $tag, ::loop_unwrap::__loop_unwrap_message!($err_msg)),
+                );
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, count: $count:expr, $err_msg:expr) => {{
+        $count += 1;
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{} (attempt {})", ::loop_unwrap::__loop_unwrap_message!($err_msg), $count));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    }};
+    ($x:expr, $label:lifetime, count: $count:expr, $err_msg:expr) => {{
+        $count += 1;
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{} (attempt {})", ::loop_unwrap::__loop_unwrap_message!($err_msg), $count));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    }};
+    ($x:expr, count: $count:expr, $err_msg:expr, $label:lifetime) => {{
+        $count += 1;
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{} (attempt {})", ::loop_unwrap::__loop_unwrap_message!($err_msg), $count));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    }};
+    ($x:expr, escalate: $fails:expr, $outer:lifetime, $pred:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                $fails += 1;
+                if ::loop_unwrap::__loop_unwrap_check(&$fails, $pred) {
+                    ::loop_unwrap::__loop_unwrap_count_break();
+                    break $outer;
+                }
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, escalate: $fails:expr, $outer:lifetime, $pred:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                $fails += 1;
+                if ::loop_unwrap::__loop_unwrap_check(&$fails, $pred) {
+                    ::loop_unwrap::__loop_unwrap_count_break();
+                    break $outer;
+                }
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, on_err: $body:block) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                $body
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, on_err: $body:block) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                $body
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, on_err: $body:block, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                $body
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, skip_if: $pred:expr) => {
+        match ::loop_unwrap::ToResultLike::into_parts($x) {
+            Ok(v) => v,
+            Err(e) if ::loop_unwrap::__loop_unwrap_check(&e, $pred) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break Err(e);
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, skip_if: $pred:expr) => {
+        match ::loop_unwrap::ToResultLike::into_parts($x) {
+            Ok(v) => v,
+            Err(e) if ::loop_unwrap::__loop_unwrap_check(&e, $pred) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err(e);
+            }
+        }
+    };
+    ($x:expr, skip_if: $pred:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToResultLike::into_parts($x) {
+            Ok(v) => v,
+            Err(e) if ::loop_unwrap::__loop_unwrap_check(&e, $pred) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err(e);
+            }
+        }
+    };
+    ($x:expr, else |$e:pat_param| $body:block) => {
+        match ::loop_unwrap::ToResultLike::into_parts($x) {
+            Ok(v) => v,
+            Err($e) => {
+                $body
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, else |$e:pat_param| $body:block) => {
+        match ::loop_unwrap::ToResultLike::into_parts($x) {
+            Ok(v) => v,
+            Err($e) => {
+                $body
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, else |$e:pat_param| $body:block, $label:lifetime) => {
+        match ::loop_unwrap::ToResultLike::into_parts($x) {
+            Ok(v) => v,
+            Err($e) => {
+                $body
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, |$e:pat_param| $err_msg:expr) => {
+        match ::loop_unwrap::ToResultLike::into_parts($x) {
+            Ok(v) => v,
+            Err($e) => {
+                ::loop_unwrap::__loop_unwrap_emit_skip(cfg!(debug_assertions), format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, |$e:pat_param| $err_msg:expr) => {
+        match ::loop_unwrap::ToResultLike::into_parts($x) {
+            Ok(v) => v,
+            Err($e) => {
+                ::loop_unwrap::__loop_unwrap_emit_skip(cfg!(debug_assertions), format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, |$e:pat_param| $err_msg:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToResultLike::into_parts($x) {
+            Ok(v) => v,
+            Err($e) => {
+                ::loop_unwrap::__loop_unwrap_emit_skip(cfg!(debug_assertions), format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $prefix:literal, |$e:pat_param| $body:expr) => {
+        match ::loop_unwrap::ToResultLike::into_parts($x) {
+            Ok(v) => v,
+            Err($e) => {
+                ::loop_unwrap::__loop_unwrap_emit_skip(cfg!(debug_assertions), format_args!("{}", $prefix));
+                $body;
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, $prefix:literal, |$e:pat_param| $body:expr) => {
+        match ::loop_unwrap::ToResultLike::into_parts($x) {
+            Ok(v) => v,
+            Err($e) => {
+                ::loop_unwrap::__loop_unwrap_emit_skip(cfg!(debug_assertions), format_args!("{}", $prefix));
+                $body;
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $prefix:literal, |$e:pat_param| $body:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToResultLike::into_parts($x) {
+            Ok(v) => v,
+            Err($e) => {
+                ::loop_unwrap::__loop_unwrap_emit_skip(cfg!(debug_assertions), format_args!("{}", $prefix));
+                $body;
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit_skip(cfg!(debug_assertions), format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit_skip(cfg!(debug_assertions), format_args!($fmt, $($args),+));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit_skip(cfg!(debug_assertions), format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit_skip(cfg!(debug_assertions), format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit_skip(cfg!(debug_assertions), format_args!($fmt, $($args),+));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr, $label:ident) => {
+        ::core::compile_error!(::core::concat!(
+            "loop labels must start with a single quote, e.g. '",
+            ::core::stringify!($label)
+        ))
+    };
+    ($x:expr, $label:ident, $($rest:tt)+) => {
+        ::core::compile_error!(::core::concat!(
+            "loop labels must start with a single quote, e.g. '",
+            ::core::stringify!($label)
+        ))
+    };
+}
+
+/// Works like `.unwrap`, if it's an Err or None, it calls `break` on the loop.
+/// Takes an optional [message and label](crate#messages-and-labels), printed when breaking.
+/// `unwrap_break!(x, const)` and `unwrap_break!(x, 'outer, const)` work in a `const fn`, for an
+/// Option only and without a message, like the `const` forms of [`unwrap_continue!`].
+/// With `with: value`, the loop breaks with `value`, so `let x = loop { ... };` gets it.
+/// It's only evaluated when breaking, and can be followed by a message.
+/// Like `value`, the message is only evaluated when breaking, and can be any [`Message`], like
+/// `|| message`.
+/// `on_err: { ... }` runs the block right before breaking, as with [`unwrap_continue!`].
+/// `summary: message` prints the message when breaking, like a message does, but reads better
+/// for a summary of what the loop did: it's evaluated only once, since the loop is left right
+/// after. It can be any [`Message`], so a closure like `|| format!("done: {}", n)` is only called
+/// then too.
+/// With `when: |v| condition`, an Ok or Some also breaks the loop if `condition` holds for the
+/// unwrapped value, for values that are valid but mean the loop is done. The loop breaks with
+/// `Some(v)` then, and with `None` on an Err or None. `when: |v| condition, with: value` breaks
+/// with `v` itself, and with `value` on an Err or None instead.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_break;
+/// loop {
+///         let input = "Not a number";
+///         let parsed_input: i32 = unwrap_break!(input.parse()); //parse returns Err for this input
+///     }
+/// println!("This line will be reached.");
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_break;
+/// loop {
+///         let input = "Not a number";
+///         let parsed_input: i32 = unwrap_break!(input.parse(), "Please Enter a Number!");
+///         // "Please Enter a Number!" is printed in console with a `println!()`
+///         //loop breaks
+///     }
+/// ```
+/// ```no_run
+/// # use loop_unwrap::unwrap_break;
+/// loop {
+///         let some_value: i32 = unwrap_break!(Some(32), "Please Enter a Number!");
+///         assert_eq!(some_value, 32_i32)
+///         //no breakage here.
+///     }
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_break;
+/// 'main: loop {
+///        loop {
+///            let n = unwrap_break!("t".parse::<i32>(), "Couldn't parse, exiting main loop", 'main);
+///            break; //<-- this line won't be reached.
+///        }
+///        println!("This line will never be reached, because 'main breaks.");
+///    }
+/// ```   
+/// ```
+/// # use loop_unwrap::unwrap_break;
+/// let mut sum = 0;
+/// for input in &["1", "two", "3"] {
+///     let n: i32 = unwrap_break!(input.parse(), "Stopped at {:?}, sum is {}", input, sum);
+///     // "Stopped at "two", sum is 1" is printed in console with a `println!()`
+///     sum += n;
+/// }
+/// assert_eq!(sum, 1);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_break;
+/// let mut rows = 0;
+/// 'main: for line in &["1 2", "3 x", "5 6"] {
+///     for word in line.split(' ') {
+///         unwrap_break!(word.parse::<i32>(), 'main, "Bad word {:?} in line {:?}", word, line);
+///     }
+///     rows += 1;
+/// }
+/// assert_eq!(rows, 1);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_break;
+/// let mut inputs = vec!["5", "not a number"].into_iter();
+/// let mut last = 0;
+/// let value = loop {
+///     let input = unwrap_break!(inputs.next(), with: last);
+///     let n: i32 = unwrap_break!(input.parse(), with: last);
+///     last = unwrap_break!(n.checked_mul(2), with: i32::MAX, "Too big!");
+/// };
+/// assert_eq!(value, 10);
+///
+/// let value = 'main: loop {
+///     for input in &["1", "x"] {
+///         let n: i32 = unwrap_break!(input.parse(), 'main, with: -1, "Couldn't parse");
+///         println!("{}", n);
+///     }
+///     break 0;
+/// };
+/// assert_eq!(value, -1);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_break;
+/// let mut built = 0;
+/// let mut inputs = vec!["1", "2", "x", "4"].into_iter();
+/// loop {
+///     let input = unwrap_break!(inputs.next());
+///     let _n: i32 = unwrap_break!(input.parse(), || {
+///         built += 1;
+///         format!("Stopped at {:?}", input)
+///     });
+/// }
+/// assert_eq!(built, 1);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_break;
+/// const END: i32 = 0;
+/// let mut inputs = vec!["3", "4", "0", "5"].into_iter();
+/// let mut sum = 0;
+/// let last = loop {
+///     let input = unwrap_break!(inputs.next(), with: None);
+///     let n: i32 = unwrap_break!(input.parse(), when: |n| *n == END, "Couldn't parse");
+///     sum += n;
+/// };
+/// assert_eq!(last, Some(END));
+/// assert_eq!(sum, 7);
+///
+/// let mut inputs = vec!["3", "x"].into_iter();
+/// let last = loop {
+///     let input = unwrap_break!(inputs.next(), with: -2);
+///     let n: i32 = unwrap_break!(input.parse(), when: |n| *n == END, with: -1);
+///     sum += n;
+/// };
+/// assert_eq!(last, -1);
+/// assert_eq!(sum, 10);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_break;
+/// let mut open_files = 0;
+/// let mut cleanups = 0;
+/// 'files: for name in &["a.txt", "b.txt", "c"] {
+///     open_files += 1;
+///     for _ in 0..2 {
+///         let _ext = unwrap_break!(name.split('.').nth(1), 'files, on_err: {
+///             open_files -= 1;
+///             cleanups += 1;
+///         });
+///     }
+///     open_files -= 1;
+/// }
+/// assert_eq!((open_files, cleanups), (0, 1));
+/// ```
+/// ```
+/// # use loop_unwrap::{set_unwrap_writer, unwrap_break};
+/// # use std::io::{self, Write};
+/// # use std::sync::{Arc, Mutex};
+/// # #[derive(Clone, Default)]
+/// # struct Captured(Arc<Mutex<Vec<u8>>>);
+/// # impl Write for Captured {
+/// #     fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.lock().unwrap().write(buf) }
+/// #     fn flush(&mut self) -> io::Result<()> { Ok(()) }
+/// # }
+/// # let captured = Captured::default();
+/// # set_unwrap_writer(Box::new(captured.clone()));
+/// let mut batch = vec!["1", "2", "3"].into_iter();
+/// let mut processed = 0;
+/// loop {
+///     let _item = unwrap_break!(batch.next(), summary: || format!("done: {}", processed));
+///     processed += 1;
+/// }
+/// for _ in 0..2 {
+///     unwrap_break!(None::<()>, summary: "nothing left");
+/// }
+/// // `captured` is a writer installed with `set_unwrap_writer`
+/// # #[cfg(not(feature = "quiet"))]
+/// assert_eq!(&*captured.0.lock().unwrap(), b"done: 3\nnothing left\n");
+/// ```
+#[macro_export]
+macro_rules! unwrap_break {
+    ($x:expr) => {{
+        let Some(v) = ::loop_unwrap::ToOption::to_option($x) else {
+            ::loop_unwrap::__loop_unwrap_count_break();
+            break;
+        };
+        v
+    }};
+    ($x:expr, const) => {{
+        let Some(v) = $x else {
+            break;
+        };
+        v
+    }};
+    ($x:expr, $label:lifetime, const) => {{
+        let Some(v) = $x else {
+            break $label;
+        };
+        v
+    }};
+    ($x:expr, const, $label:lifetime) => {{
+        let Some(v) = $x else {
+            break $label;
+        };
+        v
+    }};
+    ($x:expr, with: $value:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $value;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, with: $value:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label $value;
+            }
+        }
+    };
+    ($x:expr, with: $value:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label $value;
+            }
+        }
+    };
+    ($x:expr, with: $value:expr, $label:lifetime, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label $value;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, with: $value:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label $value;
+            }
+        }
+    };
+    ($x:expr, with: $value:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $value;
+            }
+        }
+    };
+    ($x:expr, with: $value:expr, $err_msg:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label $value;
+            }
+        }
+    };
+    ($x:expr, when: $pred:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) if ::loop_unwrap::__loop_unwrap_check(&v, $pred) => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break Some(v);
+            }
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break None;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, when: $pred:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) if ::loop_unwrap::__loop_unwrap_check(&v, $pred) => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label Some(v);
+            }
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label None;
+            }
+        }
+    };
+    ($x:expr, when: $pred:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) if ::loop_unwrap::__loop_unwrap_check(&v, $pred) => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break Some(v);
+            }
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break None;
+            }
+        }
+    };
+    ($x:expr, when: $pred:expr, with: $value:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) if ::loop_unwrap::__loop_unwrap_check(&v, $pred) => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break v;
+            }
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $value;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, when: $pred:expr, with: $value:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) if ::loop_unwrap::__loop_unwrap_check(&v, $pred) => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label v;
+            }
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label $value;
+            }
+        }
+    };
+    ($x:expr, when: $pred:expr, with: $value:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) if ::loop_unwrap::__loop_unwrap_check(&v, $pred) => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break v;
+            }
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $value;
+            }
+        }
+    };
+    ($x:expr, on_err: $body:block) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                $body
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, on_err: $body:block) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                $body
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label;
+            }
+        }
+    };
+    ($x:expr, on_err: $body:block, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                $body
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label;
+            }
+        }
+    };
+    ($x:expr, summary: $summary:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($summary)));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, summary: $summary:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($summary)));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label;
+            }
+        }
+    };
+    ($x:expr, summary: $summary:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($summary)));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime) => {{
+        let Some(v) = ::loop_unwrap::ToOption::to_option($x) else {
+            ::loop_unwrap::__loop_unwrap_count_break();
+            break $label;
+        };
+        v
+    }};
+    ($x:expr, $label:lifetime, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!($fmt, $($args),+));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label;
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break;
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label;
+            }
+        }
+    };
+    ($x:expr, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!($fmt, $($args),+));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break;
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr, $label:ident) => {
+        ::core::compile_error!(::core::concat!(
+            "loop labels must start with a single quote, e.g. '",
+            ::core::stringify!($label)
+        ))
+    };
+    ($x:expr, $label:ident, $($rest:tt)+) => {
+        ::core::compile_error!(::core::concat!(
+            "loop labels must start with a single quote, e.g. '",
+            ::core::stringify!($label)
+        ))
+    };
+}
+
+/// Works only on Result enum. If the value is Err(e), breaks the loop returning Err(e).
+/// Otherwise, it unwraps and the code continues.
+/// Supports loop labels.
+/// An Option (or anything else implementing [`ToOption`]) can be used when an error to break
+/// with is given as `err: error_value`. It's only evaluated on None, and can be followed by a
+/// message.
+/// For an Option, it can also be written as a closure, `or_err: || error_value`, to make clear
+/// it's built lazily, as in `unwrap_break_err!(map.get(key), or_err: || MyErr::NotFound)`. The
+/// label can go before or after it. It doesn't compile on a Result, whose error would be thrown
+/// away. A closure without `or_err:`, as in `unwrap_break_err!(x, || message)`, is a lazy message,
+/// as for [`unwrap_break!`].
+/// With `map: |e| new_error`, the loop breaks with `Err(new_error)` instead, for loops whose
+/// error type is different. It can be followed by a message too.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_break_err;
+/// let value = loop {
+///        let s = "not a number";
+///        let n = unwrap_break_err!(s.parse::<i32>(), "Couldn't parse number."); // breaks with error value
+///        break Ok(n + 1); //<-- this line will never be reached since the macro breaks
+///    };
+///    assert_eq!(true, value.is_err());
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_break_err;
+/// let result = 'main: loop {
+///         loop {
+///             let n = unwrap_break_err!("t".parse::<i32>(), 'main);
+///             break 'main Ok(100);
+///         }
+///     };
+///     assert_eq!(result.is_err(), true);
+/// ```    
+/// ```
+/// # use loop_unwrap::unwrap_break_err;
+/// let s = "not a number";
+/// let value: Result<i32, _> = loop {
+///     let n = unwrap_break_err!(s.parse::<i32>(), "Couldn't parse {:?}", s);
+///     break Ok(n);
+/// };
+/// assert!(value.is_err());
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_break_err;
+/// let result: Result<i32, _> = 'main: loop {
+///     loop {
+///         let n = unwrap_break_err!("t".parse::<i32>(), 'main, "Couldn't parse {:?}", "t");
+///         break 'main Ok(n);
+///     }
+/// };
+/// assert!(result.is_err());
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_break_err;
+/// use std::collections::HashMap;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum LookupError {
+///     Missing(&'static str),
+/// }
+///
+/// let ages: HashMap<_, _> = vec![("alice", 30), ("bob", 25)].into_iter().collect();
+/// let mut names = vec!["alice", "carol", "bob"].into_iter();
+/// let mut total = 0;
+/// let result = loop {
+///     let name = match names.next() {
+///         Some(name) => name,
+///         None => break Ok(total),
+///     };
+///     total += unwrap_break_err!(ages.get(name), err: LookupError::Missing(name));
+/// };
+/// assert_eq!(result, Err(LookupError::Missing("carol")));
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_break_err;
+/// let rows = [vec![Some(1), Some(2)], vec![Some(3), None]];
+/// let result: Result<(), String> = 'main: loop {
+///     for row in &rows {
+///         for cell in row {
+///             unwrap_break_err!(*cell, 'main, err: "empty cell".to_string(), "Found an empty cell");
+///         }
+///     }
+///     break Ok(());
+/// };
+/// assert_eq!(result, Err("empty cell".to_string()));
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_break_err;
+/// use std::num::ParseIntError;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum ConfigError {
+///     BadPort(ParseIntError),
+///     Missing,
+/// }
+///
+/// let mut lines = vec!["8080", "80x"].into_iter();
+/// let mut ports = Vec::new();
+/// let result: Result<(), ConfigError> = loop {
+///     let line = unwrap_break_err!(lines.next(), err: ConfigError::Missing);
+///     ports.push(unwrap_break_err!(line.parse::<u16>(), map: ConfigError::BadPort, "Bad port"));
+/// };
+/// assert!(matches!(result, Err(ConfigError::BadPort(_))));
+/// assert_eq!(ports, vec![8080]);
+///
+/// let result: Result<u16, String> = 'main: loop {
+///     for line in &["1", "x"] {
+///         unwrap_break_err!(line.parse::<u16>(), 'main, map: |e| format!("{:?}: {}", line, e));
+///     }
+///     break Ok(0);
+/// };
+/// assert_eq!(result, Err("\"x\": invalid digit found in string".to_string()));
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_break_err;
+/// use std::collections::HashMap;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum OrderError {
+///     NotFound(u32),
+///     OutOfStock,
+/// }
+///
+/// let stock: HashMap<u32, u32> = vec![(1, 5), (2, 0)].into_iter().collect();
+/// let place = |order: &[u32]| -> Result<u32, OrderError> {
+///     let mut total = 0;
+///     'items: loop {
+///         for id in order {
+///             let count = unwrap_break_err!(stock.get(id), 'items, or_err: || OrderError::NotFound(*id));
+///             let count = (*count > 0).then(|| count);
+///             total += unwrap_break_err!(count, 'items, or_err: || OrderError::OutOfStock);
+///         }
+///         break Ok(total);
+///     }
+/// };
+/// assert_eq!(place(&[1, 1]), Ok(10));
+/// assert_eq!(place(&[1, 3]), Err(OrderError::NotFound(3)));
+/// assert_eq!(place(&[2]), Err(OrderError::OutOfStock));
+/// ```
+#[macro_export]
+macro_rules! unwrap_break_err {
+    ($x:expr) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break Err(e);
+            }
+        }
+    };
+    ($x:expr, or_err: || $err:expr) => {
+        match ::loop_unwrap::IsOption::into_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break Err($err);
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, or_err: || $err:expr) => {
+        match ::loop_unwrap::IsOption::into_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err($err);
+            }
+        }
+    };
+    ($x:expr, or_err: || $err:expr, $label:lifetime) => {
+        match ::loop_unwrap::IsOption::into_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err($err);
+            }
+        }
+    };
+    ($x:expr, err: $err:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break Err($err);
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, err: $err:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err($err);
+            }
+        }
+    };
+    ($x:expr, err: $err:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err($err);
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, err: $err:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err($err);
+            }
+        }
+    };
+    ($x:expr, err: $err:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break Err($err);
+            }
+        }
+    };
+    ($x:expr, err: $err:expr, $err_msg:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err($err);
+            }
+        }
+    };
+    ($x:expr, map: $map:expr) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break Err(::loop_unwrap::__loop_unwrap_map(e, $map));
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, map: $map:expr) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err(::loop_unwrap::__loop_unwrap_map(e, $map));
+            }
+        }
+    };
+    ($x:expr, map: $map:expr, $label:lifetime) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err(::loop_unwrap::__loop_unwrap_map(e, $map));
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, map: $map:expr, $err_msg:expr) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_emit_error(
+                    format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)),
+                    ::loop_unwrap::__loop_unwrap_error_field!(e),
+                );
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err(::loop_unwrap::__loop_unwrap_map(e, $map));
+            }
+        }
+    };
+    ($x:expr, map: $map:expr, $err_msg:expr) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_emit_error(
+                    format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)),
+                    ::loop_unwrap::__loop_unwrap_error_field!(e),
+                );
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break Err(::loop_unwrap::__loop_unwrap_map(e, $map));
+            }
+        }
+    };
+    ($x:expr, map: $map:expr, $err_msg:expr, $label:lifetime) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_emit_error(
+                    format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)),
+                    ::loop_unwrap::__loop_unwrap_error_field!(e),
+                );
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err(::loop_unwrap::__loop_unwrap_map(e, $map));
+            }
+        }
+    };
+    ($x:expr, $label:lifetime) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err(e);
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, $err_msg:expr) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_emit_error(
+                    format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)),
+                    ::loop_unwrap::__loop_unwrap_error_field!(e),
+                );
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err(e);
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_emit_error(
+                    format_args!($fmt, $($args),+),
+                    ::loop_unwrap::__loop_unwrap_error_field!(e),
+                );
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err(e);
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_emit_error(
+                    format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)),
+                    ::loop_unwrap::__loop_unwrap_error_field!(e),
+                );
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break Err(e);
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr, $label:lifetime) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_emit_error(
+                    format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)),
+                    ::loop_unwrap::__loop_unwrap_error_field!(e),
+                );
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err(e);
+            }
+        }
+    };
+    ($x:expr, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_emit_error(
+                    format_args!($fmt, $($args),+),
+                    ::loop_unwrap::__loop_unwrap_error_field!(e),
+                );
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break Err(e);
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr, $label:ident) => {
+        ::core::compile_error!(::core::concat!(
+            "loop labels must start with a single quote, e.g. '",
+            ::core::stringify!($label)
+        ))
+    };
+    ($x:expr, $label:ident, $($rest:tt)+) => {
+        ::core::compile_error!(::core::concat!(
+            "loop labels must start with a single quote, e.g. '",
+            ::core::stringify!($label)
+        ))
+    };
+}
+
+/// Works only on Result enum. If the value is Err(e), calls `continue` on the loop.
+/// Unlike `unwrap_continue!`, an Option is rejected at compile time, so a Result can't be
+/// mixed up with an Option by accident.
+/// Takes an optional [message and label](crate#messages-and-labels).
+/// The error is available before continuing, as with [`unwrap_continue!`]: `|e| message` builds
+/// the message from it, and `else |e| { ... }` runs the block with it instead of printing.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue_err;
+/// let mut sum = 0;
+/// for input in &["1", "not a number", "3"] {
+///     let n = unwrap_continue_err!(input.parse::<i32>()); // continues on the second input
+///     sum += n;
+/// }
+/// assert_eq!(sum, 4);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_continue_err;
+/// let mut sum = 0;
+/// for input in &["1", "not a number", "3"] {
+///     let n = unwrap_continue_err!(input.parse::<i32>(), "Please Enter a Number!");
+///     // "Please Enter a Number!" is printed in console with a `println!()`
+///     sum += n;
+/// }
+/// assert_eq!(sum, 4);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_continue_err;
+/// let mut parsed = Vec::new();
+/// 'main: for line in &["1 2", "3 x", "5 6"] {
+///     let mut row = Vec::new();
+///     for word in line.split(' ') {
+///         row.push(unwrap_continue_err!(word.parse::<i32>(), 'main));
+///     }
+///     parsed.push(row); //<-- skipped for "3 x", since 'main is continued
+/// }
+/// assert_eq!(parsed, vec![vec![1, 2], vec![5, 6]]);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_continue_err;
+/// let mut parsed = Vec::new();
+/// 'main: for line in &["1 2", "3 x", "5 6"] {
+///     let mut row = Vec::new();
+///     for word in line.split(' ') {
+///         row.push(unwrap_continue_err!(word.parse::<i32>(), "Bad row, skipping", 'main));
+///     }
+///     parsed.push(row);
+/// }
+/// assert_eq!(parsed, vec![vec![1, 2], vec![5, 6]]);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_continue_err;
+/// let mut parsed = Vec::new();
+/// 'main: for line in &["1 2", "3 x", "5 6"] {
+///     let mut row = Vec::new();
+///     for word in line.split(' ') {
+///         row.push(unwrap_continue_err!(word.parse::<i32>(), 'main, "Bad row, skipping"));
+///     }
+///     parsed.push(row);
+/// }
+/// assert_eq!(parsed, vec![vec![1, 2], vec![5, 6]]);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_continue_err;
+/// let mut parsed = Vec::new();
+/// 'main: for line in &["1 2", "3 x", "5 6"] {
+///     let mut row = Vec::new();
+///     for word in line.split(' ') {
+///         row.push(unwrap_continue_err!(word.parse::<i32>(), 'main, "Bad word {:?}", word));
+///     }
+///     parsed.push(row);
+/// }
+/// assert_eq!(parsed, vec![vec![1, 2], vec![5, 6]]);
+///
+/// let mut sum = 0;
+/// for input in &["1", "two", "3"] {
+///     sum += unwrap_continue_err!(input.parse::<i32>(), "Couldn't parse {:?}", input);
+/// }
+/// assert_eq!(sum, 4);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_continue_err;
+/// let mut errors = Vec::new();
+/// let mut sum = 0;
+/// 'lines: for line in &["1 2", "3 x", "5"] {
+///     for word in line.split(' ') {
+///         sum += unwrap_continue_err!(word.parse::<i32>(), 'lines, else |e| {
+///             errors.push(format!("{:?}: {}", word, e));
+///         });
+///     }
+/// }
+/// assert_eq!(sum, 11);
+/// assert_eq!(errors, vec!["\"x\": invalid digit found in string"]);
+///
+/// for input in &["1", "two"] {
+///     sum += unwrap_continue_err!(input.parse::<i32>(), |e| format!("{:?}: {}", input, e));
+///     // `"two": invalid digit found in string` is printed in console with a `println!()`
+/// }
+/// assert_eq!(sum, 12);
+/// ```
+/// ```compile_fail
+/// # use loop_unwrap::unwrap_continue_err;
+/// loop {
+///     let n: i32 = unwrap_continue_err!(Some(5)); // Option isn't accepted
+///     break;
+/// }
+/// ```
+#[macro_export]
+macro_rules! unwrap_continue_err {
+    ($x:expr) => {
+        match $x {
+            Ok(v) => v,
+            Err(_) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime) => {
+        match $x {
+            Ok(v) => v,
+            Err(_) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, else |$e:pat_param| $body:block) => {
+        match $x {
+            Ok(v) => v,
+            Err($e) => {
+                $body
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, else |$e:pat_param| $body:block) => {
+        match $x {
+            Ok(v) => v,
+            Err($e) => {
+                $body
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, else |$e:pat_param| $body:block, $label:lifetime) => {
+        match $x {
+            Ok(v) => v,
+            Err($e) => {
+                $body
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, |$e:pat_param| $err_msg:expr) => {
+        match $x {
+            Ok(v) => v,
+            Err($e) => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, |$e:pat_param| $err_msg:expr) => {
+        match $x {
+            Ok(v) => v,
+            Err($e) => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, |$e:pat_param| $err_msg:expr, $label:lifetime) => {
+        match $x {
+            Ok(v) => v,
+            Err($e) => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, $err_msg:expr) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_emit_error(
+                    format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)),
+                    ::loop_unwrap::__loop_unwrap_error_field!(e),
+                );
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_emit_error(
+                    format_args!($fmt, $($args),+),
+                    ::loop_unwrap::__loop_unwrap_error_field!(e),
+                );
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_emit_error(
+                    format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)),
+                    ::loop_unwrap::__loop_unwrap_error_field!(e),
+                );
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr, $label:lifetime) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_emit_error(
+                    format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)),
+                    ::loop_unwrap::__loop_unwrap_error_field!(e),
+                );
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_emit_error(
+                    format_args!($fmt, $($args),+),
+                    ::loop_unwrap::__loop_unwrap_error_field!(e),
+                );
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr, $label:ident) => {
+        ::core::compile_error!(::core::concat!(
+            "loop labels must start with a single quote, e.g. '",
+            ::core::stringify!($label)
+        ))
+    };
+    ($x:expr, $label:ident, $($rest:tt)+) => {
+        ::core::compile_error!(::core::concat!(
+            "loop labels must start with a single quote, e.g. '",
+            ::core::stringify!($label)
+        ))
+    };
+}
+
+/// Works like `.unwrap`, if it's an Err or None, it returns from the enclosing function.
+/// Returns `Default::default()` (`()` for functions without a return type) unless a return value
+/// is provided as the second argument.
+/// Prints a [message](crate#messages-and-labels) if provided after the return value.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_return;
+/// fn sum(inputs: &[&str]) -> i32 {
+///     let mut sum = 0;
+///     for input in inputs {
+///         sum += unwrap_return!(input.parse::<i32>()); // returns 0 when parsing fails
+///     }
+///     sum
+/// }
+/// assert_eq!(sum(&["1", "2"]), 3);
+/// assert_eq!(sum(&["1", "two"]), 0);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_return;
+/// fn sum(inputs: &[&str]) -> i32 {
+///     let mut sum = 0;
+///     for input in inputs {
+///         sum += unwrap_return!(input.parse::<i32>(), -1);
+///     }
+///     sum
+/// }
+/// assert_eq!(sum(&["1", "two"]), -1);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_return;
+/// fn first_word_len(line: &str) -> usize {
+///     let word = unwrap_return!(line.split_whitespace().next(), 0, "Empty line!");
+///     // "Empty line!" is printed in console with a `println!()`
+///     word.len()
+/// }
+/// assert_eq!(first_word_len("hello world"), 5);
+/// assert_eq!(first_word_len("   "), 0);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_return;
+/// fn print_numbers(inputs: &[&str]) {
+///     for input in inputs {
+///         let n: i32 = unwrap_return!(input.parse(), (), "Stopped at {:?}", input);
+///         println!("{}", n);
+///     }
+/// }
+/// print_numbers(&["1", "two", "3"]);
+/// ```
+#[macro_export]
+macro_rules! unwrap_return {
+    ($x:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                return ::core::default::Default::default();
+            }
+        }
+    };
+    ($x:expr, $ret:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                return $ret;
+            }
+        }
+    };
+    ($x:expr, $ret:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                return $ret;
+            }
+        }
+    };
+    ($x:expr, $ret:expr, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!($fmt, $($args),+));
+                return $ret;
+            }
+        }
+    };
+}
+
+/// Works only on Result enum. If the value is Err(e), returns `Err(e)` from the enclosing function.
+/// The error is converted with `From::from` like `?` does, so the function's error type can differ
+/// from the one being unwrapped.
+/// Prints a [message](crate#messages-and-labels) if provided.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_return_err;
+/// use std::num::ParseIntError;
+///
+/// #[derive(Debug)]
+/// enum MyErr {
+///     Parse(ParseIntError),
+/// }
+///
+/// impl From<ParseIntError> for MyErr {
+///     fn from(e: ParseIntError) -> Self {
+///         MyErr::Parse(e)
+///     }
+/// }
+///
+/// fn sum(inputs: &[&str]) -> Result<i32, MyErr> {
+///     let mut sum = 0;
+///     for input in inputs {
+///         sum += unwrap_return_err!(input.parse::<i32>());
+///     }
+///     Ok(sum)
+/// }
+/// assert_eq!(sum(&["1", "2"]).unwrap(), 3);
+/// assert!(matches!(sum(&["1", "two"]), Err(MyErr::Parse(_))));
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_return_err;
+/// fn sum(inputs: &[&str]) -> Result<i32, Box<dyn std::error::Error>> {
+///     let mut sum = 0;
+///     for input in inputs {
+///         sum += unwrap_return_err!(input.parse::<i32>(), "Couldn't parse {:?}", input);
+///         // "Couldn't parse "two"" is printed in console with a `println!()`
+///     }
+///     Ok(sum)
+/// }
+/// assert!(sum(&["1", "two"]).is_err());
+/// ```
+#[macro_export]
+macro_rules! unwrap_return_err {
+    ($x:expr) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                return Err(::core::convert::From::from(e));
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_emit_error(
+                    format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)),
+                    ::loop_unwrap::__loop_unwrap_error_field!(e),
+                );
+                return Err(::core::convert::From::from(e));
+            }
+        }
+    };
+    ($x:expr, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) => {
+                ::loop_unwrap::__loop_unwrap_emit_error(
+                    format_args!($fmt, $($args),+),
+                    ::loop_unwrap::__loop_unwrap_error_field!(e),
+                );
+                return Err(::core::convert::From::from(e));
+            }
+        }
+    };
+}
+
+/// Works only on Result enum. If the value is Err(e) and the predicate returns true for it (it
+/// gets `&e`), `continue` is called on the loop, as for an error worth retrying. Any other error
+/// is returned from the enclosing function like [`unwrap_return_err!`] does, converted with
+/// `From::from` like `?`.
+/// If loop label is provided, the specified loop will be continued.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue_or_return;
+/// use std::error::Error;
+/// use std::io::{self, ErrorKind};
+///
+/// fn read_all(mut reads: Vec<io::Result<u8>>) -> Result<Vec<u8>, Box<dyn Error>> {
+///     reads.reverse();
+///     let mut bytes = Vec::new();
+///     while let Some(read) = reads.pop() {
+///         let byte = unwrap_continue_or_return!(read, |e| e.kind() == ErrorKind::Interrupted);
+///         bytes.push(byte);
+///     }
+///     Ok(bytes)
+/// }
+///
+/// let interrupted = || Err(io::Error::from(ErrorKind::Interrupted));
+/// assert_eq!(read_all(vec![Ok(1), interrupted(), Ok(2)]).unwrap(), vec![1, 2]);
+/// let failed = read_all(vec![Ok(1), Err(io::Error::from(ErrorKind::NotFound)), Ok(2)]);
+/// assert_eq!(failed.unwrap_err().to_string(), "entity not found");
+/// ```
+#[macro_export]
+macro_rules! unwrap_continue_or_return {
+    ($x:expr, $pred:expr) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) if ::loop_unwrap::__loop_unwrap_check(&e, $pred) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+            Err(e) => {
+                return Err(::core::convert::From::from(e));
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, $pred:expr) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) if ::loop_unwrap::__loop_unwrap_check(&e, $pred) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+            Err(e) => {
+                return Err(::core::convert::From::from(e));
+            }
+        }
+    };
+    ($x:expr, $pred:expr, $label:lifetime) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) if ::loop_unwrap::__loop_unwrap_check(&e, $pred) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+            Err(e) => {
+                return Err(::core::convert::From::from(e));
+            }
+        }
+    };
+}
+
+/// Works only on Result enum. Asks the error whether it's worth skipping through [`SkipError`]:
+/// if `is_skip()` returns true, `continue` is called on the loop, otherwise the error is returned
+/// from the enclosing function like [`unwrap_return_err!`] does, converted with `From::from` like
+/// `?`. Error types that don't implement [`SkipError`] are always skipped, as with
+/// [`unwrap_continue!`].
+/// It's [`unwrap_continue_or_return!`] with the predicate given by the error type, so every call
+/// site handling that error agrees on which ones are skipped.
+/// If loop label is provided, the specified loop will be continued.
+/// # Examples
+/// ```
+/// # use loop_unwrap::{unwrap_continue_classified, SkipError};
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum RowError {
+///     Blank,
+///     Corrupt(usize),
+/// }
+///
+/// impl SkipError for RowError {
+///     fn is_skip(&self) -> bool {
+///         *self == RowError::Blank
+///     }
+/// }
+///
+/// fn sum(rows: &[Result<i32, RowError>]) -> Result<i32, RowError> {
+///     let mut sum = 0;
+///     for row in rows.iter().cloned() {
+///         sum += unwrap_continue_classified!(row);
+///     }
+///     Ok(sum)
+/// }
+///
+/// assert_eq!(sum(&[Ok(1), Err(RowError::Blank), Ok(2)]), Ok(3));
+/// assert_eq!(sum(&[Ok(1), Err(RowError::Corrupt(2)), Ok(3)]), Err(RowError::Corrupt(2)));
+/// ```
+#[macro_export]
+macro_rules! unwrap_continue_classified {
+    ($x:expr) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) if ::loop_unwrap::__loop_unwrap_is_skip!(e) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+            Err(e) => {
+                return Err(::core::convert::From::from(e));
+            }
+        }
+    };
+    ($x:expr, $label:lifetime) => {
+        match $x {
+            Ok(v) => v,
+            Err(e) if ::loop_unwrap::__loop_unwrap_is_skip!(e) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+            Err(e) => {
+                return Err(::core::convert::From::from(e));
+            }
+        }
+    };
+}
+
+/// Works like `.unwrap_or`, if it's an Err or None, it evaluates to the provided default value
+/// instead, leaving the control flow of the loop alone.
+/// The default is only evaluated when it's needed. Without one, `Default::default()` is used.
+/// Prints a [message](crate#messages-and-labels) if provided after the default value.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_default;
+/// let mut values = Vec::new();
+/// for input in &["1", "two", "3"] {
+///     values.push(unwrap_default!(input.parse::<i32>(), -1));
+/// }
+/// assert_eq!(values, vec![1, -1, 3]);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_default;
+/// let names = vec![Some("a"), None, Some("c")];
+/// let mut joined = String::new();
+/// for name in names {
+///     joined.push_str(unwrap_default!(name, "_", "Missing name, using a placeholder"));
+///     // "Missing name, using a placeholder" is printed in console with a `println!()`
+/// }
+/// assert_eq!(joined, "a_c");
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_default;
+/// let mut sum = 0;
+/// for input in &["1", "two", "3"] {
+///     sum += unwrap_default!(input.parse::<i32>()); // 0 for "two"
+///     sum += unwrap_default!(input.parse::<i32>(), 0, "Couldn't parse {:?}, using 0", input);
+/// }
+/// assert_eq!(sum, 8);
+/// ```
+#[macro_export]
+macro_rules! unwrap_default {
+    ($x:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => ::core::default::Default::default(),
+        }
+    };
+    ($x:expr, $default:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => $default,
+        }
+    };
+    ($x:expr, $default:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                $default
+            }
+        }
+    };
+    ($x:expr, $default:expr, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!($fmt, $($args),+));
+                $default
+            }
+        }
+    };
+}
+
+/// Works like `.unwrap`, retrying the loop on an Err or None until it failed `max` times.
+/// On every failure, `counter` is incremented. If it reached `max`, `break` is called on the loop,
+/// otherwise `continue` is.
+/// `max` counts failed attempts, not retries: with a max of 3, the loop gives up on the third
+/// failure, after the first attempt and 2 retries. `counter` is never reset by the macro, so it
+/// counts failures in total, not in a row.
+/// The counter is a variable from outside the loop, so it lives across iterations.
+/// Prints a [message](crate#messages-and-labels) on every failure if provided, and takes a
+/// [label](crate#messages-and-labels) too.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_retry;
+/// let mut attempts = 0;
+/// let mut failures = 0;
+/// loop {
+///     attempts += 1;
+///     let n: i32 = unwrap_retry!("not a number".parse(), failures, 3, "Couldn't parse, retrying");
+///     println!("{}", n); //<-- never reached
+/// }
+/// assert_eq!(attempts, 3);
+/// assert_eq!(failures, 3);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_retry;
+/// let mut inputs = vec!["x", "y", "7"].into_iter();
+/// let mut failures = 0;
+/// let mut parsed = None;
+/// 'main: loop {
+///     let input = inputs.next().unwrap();
+///     for word in input.split(' ') {
+///         parsed = Some(unwrap_retry!(word.parse::<i32>(), failures, 3, 'main, "Retrying"));
+///     }
+///     break;
+/// }
+/// assert_eq!(parsed, Some(7)); // the third attempt succeeded, so it didn't give up
+/// assert_eq!(failures, 2);
+/// ```
+#[macro_export]
+macro_rules! unwrap_retry {
+    ($x:expr, $counter:expr, $max:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                $counter += 1;
+                if $counter >= $max {
+                    ::loop_unwrap::__loop_unwrap_count_break();
+                    break;
+                }
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $counter:expr, $max:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                $counter += 1;
+                if $counter >= $max {
+                    ::loop_unwrap::__loop_unwrap_count_break();
+                    break $label;
+                }
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $counter:expr, $max:expr, $label:lifetime, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                $counter += 1;
+                if $counter >= $max {
+                    ::loop_unwrap::__loop_unwrap_count_break();
+                    break $label;
+                }
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $counter:expr, $max:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                $counter += 1;
+                if $counter >= $max {
+                    ::loop_unwrap::__loop_unwrap_count_break();
+                    break;
+                }
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $counter:expr, $max:expr, $err_msg:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                $counter += 1;
+                if $counter >= $max {
+                    ::loop_unwrap::__loop_unwrap_count_break();
+                    break $label;
+                }
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+}
+
+/// Works like [`unwrap_retry!`], sleeping between the attempts: on an Err or None, `attempts` is
+/// incremented, and once it reached `max`, `break` is called on the loop. Otherwise, the thread
+/// sleeps for `base * 2^(attempts - 1)` (so `base` after the first failure, then twice that, and
+/// so on), then `continue` is called. There's no sleep after the last failure.
+/// That's `base * 2^attempt` with `attempt` counted from 0: `attempts` has already been
+/// incremented for the failure when the sleep is computed, so it starts at 1.
+/// With `cap: duration` after `base`, the sleep is never longer than `duration`. Without it, it's
+/// capped at [`DEFAULT_BACKOFF_CAP`], a minute, so a large `max` doesn't leave the thread asleep
+/// for hours.
+/// `attempts` is an integer variable from outside the loop, and is never reset by the macro.
+/// If loop label is provided, the specified loop will be continued or break;-ed.
+/// Only available with the `std` feature.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_retry_backoff;
+/// use std::time::Duration;
+///
+/// let mut replies = vec![Err("busy"), Err("busy"), Ok("ready")].into_iter();
+/// let mut attempts = 0;
+/// let mut reply = None;
+/// while reply.is_none() {
+///     reply = Some(unwrap_retry_backoff!(
+///         replies.next().unwrap(),
+///         attempts: attempts,
+///         max: 5,
+///         base: Duration::from_millis(1),
+///         cap: Duration::from_millis(2)
+///     ));
+/// }
+/// assert_eq!((reply, attempts), (Some("ready"), 2)); // slept 1ms, then 2ms
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! unwrap_retry_backoff {
+    ($x:expr, attempts: $attempts:expr, max: $max:expr, base: $base:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                $attempts += 1;
+                if $attempts >= $max {
+                    ::loop_unwrap::__loop_unwrap_count_break();
+                    break;
+                }
+                ::std::thread::sleep(::loop_unwrap::__loop_unwrap_backoff(
+                    $base,
+                    $attempts,
+                    ::loop_unwrap::DEFAULT_BACKOFF_CAP,
+                ));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, attempts: $attempts:expr, max: $max:expr, base: $base:expr, cap: $cap:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                $attempts += 1;
+                if $attempts >= $max {
+                    ::loop_unwrap::__loop_unwrap_count_break();
+                    break;
+                }
+                ::std::thread::sleep(::loop_unwrap::__loop_unwrap_backoff(
+                    $base,
+                    $attempts,
+                    $cap,
+                ));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, attempts: $attempts:expr, max: $max:expr, base: $base:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                $attempts += 1;
+                if $attempts >= $max {
+                    ::loop_unwrap::__loop_unwrap_count_break();
+                    break $label;
+                }
+                ::std::thread::sleep(::loop_unwrap::__loop_unwrap_backoff(
+                    $base,
+                    $attempts,
+                    ::loop_unwrap::DEFAULT_BACKOFF_CAP,
+                ));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, attempts: $attempts:expr, max: $max:expr, base: $base:expr, cap: $cap:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                $attempts += 1;
+                if $attempts >= $max {
+                    ::loop_unwrap::__loop_unwrap_count_break();
+                    break $label;
+                }
+                ::std::thread::sleep(::loop_unwrap::__loop_unwrap_backoff(
+                    $base,
+                    $attempts,
+                    $cap,
+                ));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+}
+
+/// Works like [`unwrap_continue!`], but also calls `continue` if the unwrapped value doesn't
+/// pass the predicate given as the second argument. The predicate gets a reference to the value.
+/// Takes an optional [message and label](crate#messages-and-labels) after the predicate.
+/// The message is printed for both kinds of failure.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue_if;
+/// let mut percentages = Vec::new();
+/// for input in &["50", "two", "150", "0"] {
+///     let n: i32 = unwrap_continue_if!(input.parse(), |n| (0..=100).contains(n), "out of range");
+///     // "out of range" is printed for "two" and "150"
+///     percentages.push(n);
+/// }
+/// assert_eq!(percentages, vec![50, 0]);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_continue_if;
+/// let mut rows = Vec::new();
+/// 'main: for line in &["1 2", "3 -4", "5 x"] {
+///     let mut row = Vec::new();
+///     for word in line.split(' ') {
+///         row.push(unwrap_continue_if!(word.parse::<i32>(), |n| *n > 0, 'main));
+///     }
+///     rows.push(row);
+/// }
+/// assert_eq!(rows, vec![vec![1, 2]]);
+/// ```
+#[macro_export]
+macro_rules! unwrap_continue_if {
+    ($x:expr, $pred:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) if ::loop_unwrap::__loop_unwrap_check(&v, $pred) => v,
+            _ => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $pred:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) if ::loop_unwrap::__loop_unwrap_check(&v, $pred) => v,
+            _ => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $pred:expr, $label:lifetime, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) if ::loop_unwrap::__loop_unwrap_check(&v, $pred) => v,
+            _ => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $pred:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) if ::loop_unwrap::__loop_unwrap_check(&v, $pred) => v,
+            _ => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $pred:expr, $err_msg:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) if ::loop_unwrap::__loop_unwrap_check(&v, $pred) => v,
+            _ => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+}
+
+/// Works like [`unwrap_continue!`], but gives up after too many failures in a row:
+/// `fails` is incremented on every Err or None and set back to 0 on every success, and once it
+/// reaches `max` the loop is broken instead of continued.
+/// So `max` limits the *consecutive* failures, not the total: failures separated by a success
+/// never add up to `max`. To give up after a total number of failures, see the `escalate:` form
+/// of [`unwrap_continue!`], which never resets its counter.
+/// With a label, that's the loop broken once `max` is reached, while `continue` is still called
+/// on the innermost loop before that.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue_until;
+/// let inputs = ["1", "x", "2", "x", "x", "3", "x", "x", "x", "4"];
+/// let mut fails = 0;
+/// let mut sum = 0;
+/// for input in &inputs {
+///     sum += unwrap_continue_until!(input.parse::<i32>(), fails: fails, max: 3);
+/// }
+/// // six failures in total, but only the last three were in a row
+/// assert_eq!((sum, fails), (6, 3));
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_continue_until;
+/// let mut fails = 0;
+/// let mut read = Vec::new();
+/// 'files: for file in &[["1", "x"], ["x", "2"]] {
+///     for line in file {
+///         read.push(unwrap_continue_until!(line.parse::<i32>(), fails: fails, max: 2, 'files));
+///     }
+/// }
+/// assert_eq!(read, vec![1]);
+/// ```
+#[macro_export]
+macro_rules! unwrap_continue_until {
+    ($x:expr, fails: $fails:expr, max: $max:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => {
+                $fails = 0;
+                v
+            }
+            None => {
+                $fails += 1;
+                if $fails >= $max {
+                    ::loop_unwrap::__loop_unwrap_count_break();
+                    break;
+                }
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, fails: $fails:expr, max: $max:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => {
+                $fails = 0;
+                v
+            }
+            None => {
+                $fails += 1;
+                if $fails >= $max {
+                    ::loop_unwrap::__loop_unwrap_count_break();
+                    break $label;
+                }
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+}
+
+/// Works like [`unwrap_continue!`], but pushes the unwrapped value into the collection given as
+/// the second argument instead of evaluating to it.
+/// Any collection implementing `Extend` works, like a `Vec`, a `HashSet` or a `String`.
+/// Takes an optional [message and label](crate#messages-and-labels) after the collection.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_push_continue;
+/// let mut numbers = Vec::new();
+/// for input in &["1", "two", "3"] {
+///     unwrap_push_continue!(input.parse::<i32>(), numbers, "Please Enter a Number!");
+///     // "Please Enter a Number!" is printed in console with a `println!()`
+/// }
+/// assert_eq!(numbers, vec![1, 3]);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_push_continue;
+/// use std::collections::BTreeSet;
+///
+/// let mut initials = BTreeSet::new();
+/// 'main: for line in &["alice bob", "carol ", "dave"] {
+///     for name in line.split(' ') {
+///         unwrap_push_continue!(name.chars().next(), initials, 'main);
+///     }
+/// }
+/// assert_eq!(initials.into_iter().collect::<String>(), "abcd");
+/// ```
+#[macro_export]
+macro_rules! unwrap_push_continue {
+    ($x:expr, $collection:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => $collection.extend(::core::iter::once(v)),
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $collection:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => $collection.extend(::core::iter::once(v)),
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $collection:expr, $label:lifetime, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => $collection.extend(::core::iter::once(v)),
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $collection:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => $collection.extend(::core::iter::once(v)),
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $collection:expr, $err_msg:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => $collection.extend(::core::iter::once(v)),
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+}
+
+/// Same as [`unwrap_continue!`], but `.await`s the expression first, so it can be given a future
+/// directly.
+/// Takes an optional [message and label](crate#messages-and-labels).
+/// The keyword forms of [`unwrap_continue!`], like `via:` or `else |e|`, aren't accepted.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue_await;
+/// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+/// #     use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+/// #     fn raw_waker() -> RawWaker {
+/// #         fn clone(_: *const ()) -> RawWaker { raw_waker() }
+/// #         fn noop(_: *const ()) {}
+/// #         static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+/// #         RawWaker::new(std::ptr::null(), &VTABLE)
+/// #     }
+/// #     let waker = unsafe { Waker::from_raw(raw_waker()) };
+/// #     let mut context = Context::from_waker(&waker);
+/// #     let mut future = Box::pin(future);
+/// #     loop {
+/// #         if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+/// async fn fetch(id: u32) -> Option<String> {
+///     if id % 2 == 0 {
+///         Some(format!("item {}", id))
+///     } else {
+///         None
+///     }
+/// }
+///
+/// let items = block_on(async {
+///     let mut items = Vec::new();
+///     for id in 0..4 {
+///         items.push(unwrap_continue_await!(fetch(id), "Item {} is missing", id));
+///     }
+///     items
+/// });
+/// assert_eq!(items, vec!["item 0", "item 2"]);
+/// ```
+#[macro_export]
+macro_rules! unwrap_continue_await {
+    ($x:expr $(, $($rest:tt)+)?) => {
+        ::loop_unwrap::__loop_unwrap_labelled!(continue, $x.await $(, $($rest)+)?)
+    };
+}
+
+/// Calls `.next()` on the iterator, and works like [`unwrap_break!`] on the result: when the
+/// iterator is exhausted, it calls `break` on the loop.
+/// The iterator is borrowed mutably, so every call advances it.
+/// Takes an optional [message and label](crate#messages-and-labels).
+/// The keyword forms of [`unwrap_break!`], like `with:` or `on_err:`, aren't accepted.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_next;
+/// let mut words = vec!["a", "b", "c"].into_iter();
+/// let mut collected = Vec::new();
+/// loop {
+///     let word = unwrap_next!(words, "Out of words");
+///     // "Out of words" is printed in console with a `println!()` at the end
+///     collected.push(word);
+/// }
+/// assert_eq!(collected, vec!["a", "b", "c"]);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_next;
+/// let mut numbers = vec![1, 2, 3, 4, 5].into_iter();
+/// let mut pairs = Vec::new();
+/// 'main: loop {
+///     loop {
+///         let a = unwrap_next!(numbers, 'main);
+///         let b = unwrap_next!(numbers, 'main, "{} has no pair", a);
+///         pairs.push((a, b));
+///     }
+/// }
+/// assert_eq!(pairs, vec![(1, 2), (3, 4)]);
+/// ```
+#[macro_export]
+macro_rules! unwrap_next {
+    ($iter:expr $(, $($rest:tt)+)?) => {
+        ::loop_unwrap::__loop_unwrap_labelled!(
+            break,
+            ::core::iter::Iterator::next(&mut $iter)
+            $(, $($rest)+)?
+        )
+    };
+}
+
+/// Loops over an iterator of Results (or Options), running the first closure-like body with
+/// every Ok value bound to its argument, and skipping the Errs, like `unwrap_continue!` on each
+/// item would. With `on_err: |e| body`, that body runs with each error (`()` for a None) first.
+/// The bodies are the loop body, so `continue` and `break` can be used in them.
+/// # Examples
+/// ```
+/// # use loop_unwrap::for_each_ok;
+/// let results: Vec<Result<i32, String>> = vec![Ok(1), Err(String::from("bad")), Ok(3)];
+/// let mut sum = 0;
+/// let mut errors = Vec::new();
+/// for_each_ok!(results, |n| sum += n, on_err: |e| errors.push(e));
+/// assert_eq!(sum, 4);
+/// assert_eq!(errors, vec!["bad"]);
+/// ```
+/// ```
+/// # use loop_unwrap::for_each_ok;
+/// let mut parsed = Vec::new();
+/// for_each_ok!("1 x 3 4".split(' ').map(str::parse::<i32>), |n| {
+///     if n > 3 {
+///         break;
+///     }
+///     parsed.push(n);
+/// });
+/// assert_eq!(parsed, vec![1, 3]);
+/// ```
+#[macro_export]
+macro_rules! for_each_ok {
+    ($iter:expr, |$v:pat_param| $body:expr) => {
+        for item in $iter {
+            match ::loop_unwrap::ToResultLike::into_parts(item) {
+                Ok($v) => {
+                    $body;
+                }
+                Err(_) => {
+                    ::loop_unwrap::__loop_unwrap_count_continue();
+                    continue;
+                }
+            }
+        }
+    };
+    ($iter:expr, |$v:pat_param| $body:expr, on_err: |$e:pat_param| $err_body:expr) => {
+        for item in $iter {
+            match ::loop_unwrap::ToResultLike::into_parts(item) {
+                Ok($v) => {
+                    $body;
+                }
+                Err($e) => {
+                    $err_body;
+                    ::loop_unwrap::__loop_unwrap_count_continue();
+                    continue;
+                }
+            }
+        }
+    };
+}
+
+/// Same as [`unwrap_continue!`], but unwraps one more level of Option: it calls `continue` on
+/// the loop unless the value is `Some(Some(v))`, and evaluates to `v`.
+/// Takes an optional [message and label](crate#messages-and-labels).
+///
+/// This is a separate macro rather than a [`ToOption`] impl for `Option<Option<T>>`, because
+/// such an impl would sit next to the one for `Option<T>`, and leave every `unwrap_continue!` on a
+/// nested Option without a type annotation ambiguous.
+///
+/// A `Result<Option<T>, E>` works the same way, since the `Result` is converted first: both an
+/// `Err` and an `Ok(None)` call `continue`, and only `Ok(Some(v))` evaluates to `v`.
+/// `unwrap_continue!` on it would only skip the `Err`, and evaluate to the inner `Option<T>`.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue_flat;
+/// use std::collections::HashMap;
+///
+/// let mut nicknames = HashMap::new();
+/// nicknames.insert("alice", Some("ali"));
+/// nicknames.insert("bob", None);
+///
+/// let mut found = Vec::new();
+/// for name in &["alice", "bob", "carol"] {
+///     // None for carol, who isn't in the map, and Some(None) for bob, who has no nickname
+///     let nickname = unwrap_continue_flat!(nicknames.get(name).copied(), "No nickname for {}", name);
+///     found.push(nickname);
+/// }
+/// assert_eq!(found, vec!["ali"]);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_continue_flat;
+/// let rows: Vec<Result<Option<i32>, String>> = vec![
+///     Ok(Some(1)),
+///     Ok(None),
+///     Err(String::from("connection lost")),
+///     Ok(Some(4)),
+/// ];
+/// let mut values = Vec::new();
+/// for row in rows {
+///     values.push(unwrap_continue_flat!(row, "Skipped a row"));
+/// }
+/// assert_eq!(values, vec![1, 4]);
+/// ```
+#[macro_export]
+macro_rules! unwrap_continue_flat {
+    ($x:expr $(, $($rest:tt)+)?) => {
+        ::loop_unwrap::__loop_unwrap_labelled!(
+            continue,
+            match ::loop_unwrap::ToOption::to_option($x) {
+                Some(Some(v)) => Some(v),
+                _ => None,
+            }
+            $(, $($rest)+)?
+        )
+    };
+}
+
+/// Same as [`unwrap_continue!`], but for integers: zero calls `continue`, and any other value,
+/// including a negative one, is evaluated to as it is. For counts and ids where zero means
+/// "nothing", without going through `NonZeroU32::new` and friends.
+/// Takes an optional [message and label](crate#messages-and-labels).
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue_nonzero;
+/// let deltas: [i32; 4] = [3, 0, -2, 0];
+/// let mut applied = Vec::new();
+/// for delta in &deltas {
+///     applied.push(unwrap_continue_nonzero!(*delta)); // -2 isn't skipped
+/// }
+/// assert_eq!(applied, vec![3, -2]);
+///
+/// let sizes: [u64; 3] = [0, 4096, 0];
+/// let mut total = 0;
+/// for size in &sizes {
+///     total += unwrap_continue_nonzero!(*size, "Skipping empty file");
+/// }
+/// assert_eq!(total, 4096);
+/// ```
+#[macro_export]
+macro_rules! unwrap_continue_nonzero {
+    ($x:expr $(, $($rest:tt)+)?) => {
+        ::loop_unwrap::__loop_unwrap_labelled!(
+            continue,
+            match $x {
+                v if v != 0 => Some(v),
+                _ => None,
+            }
+            $(, $($rest)+)?
+        )
+    };
+}
+
+/// Same as [`unwrap_continue!`], but for collections: an empty one calls `continue`, and any
+/// other is evaluated to as it is. For steps returning a `Vec` or a `String` where empty means
+/// "nothing found", without wrapping it in an Option first.
+/// Works on anything implementing [`IsEmpty`], including references to it.
+/// Takes an optional [message and label](crate#messages-and-labels).
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue_nonempty;
+/// let mut lines = Vec::new();
+/// for line in "a b\n\nc".lines() {
+///     let words: Vec<&str> = unwrap_continue_nonempty!(line.split_whitespace().collect());
+///     lines.push(words);
+/// }
+/// assert_eq!(lines, vec![vec!["a", "b"], vec!["c"]]);
+///
+/// let mut names = String::new();
+/// for name in &["alice", "", "bob"] {
+///     names += unwrap_continue_nonempty!(*name, "Skipping empty name");
+/// }
+/// assert_eq!(names, "alicebob");
+/// ```
+#[macro_export]
+macro_rules! unwrap_continue_nonempty {
+    ($x:expr $(, $($rest:tt)+)?) => {
+        ::loop_unwrap::__loop_unwrap_labelled!(
+            continue,
+            match $x {
+                v if !::loop_unwrap::IsEmpty::is_empty(&v) => Some(v),
+                _ => None,
+            }
+            $(, $($rest)+)?
+        )
+    };
+}
+
+/// Same as [`unwrap_continue!`], but only accepts an Option: a Result fails to compile, so an
+/// error can't be dropped by accident, and has to be handled or converted with `.ok()` first.
+/// Takes an optional [message and label](crate#messages-and-labels).
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue_some;
+/// let words = ["apple", "", "cherry"];
+/// let mut firsts = Vec::new();
+/// for word in &words {
+///     firsts.push(unwrap_continue_some!(word.chars().next(), "Empty word"));
+/// }
+/// assert_eq!(firsts, vec!['a', 'c']);
+/// ```
+/// ```compile_fail
+/// # use loop_unwrap::unwrap_continue_some;
+/// for input in &["1", "two"] {
+///     let n: i32 = unwrap_continue_some!(input.parse()); // a Result isn't accepted
+/// }
+/// ```
+#[macro_export]
+macro_rules! unwrap_continue_some {
+    ($x:expr $(, $($rest:tt)+)?) => {
+        ::loop_unwrap::__loop_unwrap_labelled!(
+            continue,
+            ::loop_unwrap::IsOption::into_option($x)
+            $(, $($rest)+)?
+        )
+    };
+}
+
+/// Same as [`unwrap_continue!`], but only an Option's `None` calls `continue`: an `Err`
+/// panics, like `.unwrap()` would. For loops where a missing value is expected,
+/// but an error is a bug that shouldn't be skipped over silently.
+/// Takes an optional [message and label](crate#messages-and-labels), printed on `None`.
+/// Uses [`ToOption::into_strict_option`] instead of [`ToOption::to_option`].
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue_strict;
+/// let scores = [Some(3), None, Some(4)];
+/// let mut total = 0;
+/// for score in &scores {
+///     total += unwrap_continue_strict!(*score, "No score");
+/// }
+/// assert_eq!(total, 7);
+/// ```
+/// ```should_panic
+/// # use loop_unwrap::unwrap_continue_strict;
+/// for input in &["1", "two"] {
+///     let _n: i32 = unwrap_continue_strict!(input.parse()); // panics on "two"
+/// }
+/// ```
+#[macro_export]
+macro_rules! unwrap_continue_strict {
+    ($x:expr $(, $($rest:tt)+)?) => {
+        ::loop_unwrap::__loop_unwrap_labelled!(
+            continue,
+            ::loop_unwrap::ToOption::into_strict_option($x)
+            $(, $($rest)+)?
+        )
+    };
+}
+
+/// Same as [`unwrap_continue!`], but the printed message starts with the file and line of the
+/// call, as `[src/main.rs:12] message`, to tell apart sites sharing the same message.
+/// Without a message, only the location is printed.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue_loc;
+/// for input in &["1", "two", "3"] {
+///     let _n: i32 = unwrap_continue_loc!(input.parse(), "Couldn't parse {:?}", input);
+///     // prints e.g. `[src/main.rs:4] Couldn't parse "two"`
+/// }
+/// ```
+#[macro_export]
+macro_rules! unwrap_continue_loc {
+    ($x:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("[{}:{}]", file!(), line!()));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("[{}:{}]", file!(), line!()));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("[{}:{}] {}", file!(), line!(), ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!(
+                    "[{}:{}] {}",
+                    file!(),
+                    line!(),
+                    format_args!($fmt, $($args),+)
+                ));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("[{}:{}] {}", file!(), line!(), ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("[{}:{}] {}", file!(), line!(), ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!(
+                    "[{}:{}] {}",
+                    file!(),
+                    line!(),
+                    format_args!($fmt, $($args),+)
+                ));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+}
+
+/// Same as [`unwrap_continue!`], but takes the index of the current item, like the `i` of
+/// `for (i, item) in items.iter().enumerate()`, and passes it to the format string as its first
+/// argument, before the ones given after it. Without a format string, `failed at {i}` is printed.
+/// A [label](crate#messages-and-labels) can be provided before the index.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue_at;
+/// let mut sum = 0;
+/// for (i, input) in ["1", "two", "3"].iter().enumerate() {
+///     sum += unwrap_continue_at!(input.parse::<i32>(), i, "failed at {}: {:?}", input);
+///     // only "failed at 1: \"two\"" is printed
+/// }
+/// assert_eq!(sum, 4);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_continue_at;
+/// let mut rows = 0;
+/// 'rows: for (row, line) in ["1 2", "3 x"].iter().enumerate() {
+///     for word in line.split(' ') {
+///         unwrap_continue_at!(word.parse::<i32>(), 'rows, row, "row {} has a bad number");
+///     }
+///     rows += 1;
+/// }
+/// assert_eq!(rows, 1);
+/// ```
+#[macro_export]
+macro_rules! unwrap_continue_at {
+    ($x:expr, $label:lifetime, $i:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("failed at {}", $i));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, $i:expr, $fmt:literal $(, $args:expr)* $(,)?) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!($fmt, $i $(, $args)*));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $i:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("failed at {}", $i));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $i:expr, $fmt:literal $(, $args:expr)* $(,)?) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!($fmt, $i $(, $args)*));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+}
+
+/// Works like `.unwrap`, if it's an Err or None, it breaks the loop with the last good value,
+/// given as the second argument. Otherwise, it evaluates to the new value, so the caller can
+/// store it as the last good one.
+/// It's a shorthand for `unwrap_break!(x, with: last_good)`, and accepts the same labels and
+/// messages after the last good value.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_break_or;
+/// let readings = ["20.5", "21.0", "error", "22.5"];
+/// let mut i = 0;
+/// let mut last_good = 0.0;
+/// let value = loop {
+///     last_good = unwrap_break_or!(readings[i].parse::<f64>(), last_good, "Bad reading");
+///     i += 1;
+/// };
+/// assert_eq!(value, 21.0);
+/// assert_eq!(i, 2);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_break_or;
+/// let batches = [vec!["1", "2"], vec!["3", "x"]];
+/// let mut last_good = 0;
+/// let value = 'main: loop {
+///     for batch in &batches {
+///         for input in batch {
+///             last_good = unwrap_break_or!(input.parse::<i32>(), last_good, 'main);
+///         }
+///     }
+///     break last_good;
+/// };
+/// assert_eq!(value, 3);
+/// ```
+#[macro_export]
+macro_rules! unwrap_break_or {
+    ($x:expr, $last_good:expr) => {
+        ::loop_unwrap::unwrap_break!($x, with: $last_good)
+    };
+    ($x:expr, $last_good:expr, $($rest:tt)+) => {
+        ::loop_unwrap::unwrap_break!($x, with: $last_good, $($rest)+)
+    };
+}
+
+/// Works like `.unwrap`, if it's an Err or None, it breaks the loop with `Default::default()`,
+/// so the loop's value type has to implement `Default`. Otherwise, it evaluates to the value.
+/// It's a shorthand for `unwrap_break!(x, with: Default::default())`, and accepts the same labels
+/// and messages after the expression.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_break_default;
+/// let inputs = ["1", "x", "5"];
+/// let mut i = 0;
+/// let scaled: i32 = loop {
+///     let n: i32 = unwrap_break_default!(inputs[i].parse(), "Not a number, giving up");
+///     if n > 2 {
+///         break n * 10;
+///     }
+///     i += 1;
+/// };
+/// assert_eq!(scaled, 0); // "x" was reached before "5"
+///
+/// let value: i32 = loop {
+///     break unwrap_break_default!("x".parse::<i32>());
+/// };
+/// assert_eq!(value, 0); // `i32::default()`
+/// ```
+#[macro_export]
+macro_rules! unwrap_break_default {
+    ($x:expr) => {
+        ::loop_unwrap::unwrap_break!($x, with: ::core::default::Default::default())
+    };
+    ($x:expr, $($rest:tt)+) => {
+        ::loop_unwrap::unwrap_break!($x, with: ::core::default::Default::default(), $($rest)+)
+    };
+}
+
+/// Works like `.unwrap`, but instead of calling `continue` or `break`, it evaluates to a
+/// [`ControlFlow`](core::ops::ControlFlow): `Continue(value)` if it's an Ok or Some, and
+/// `Break(())` if it's an Err or None. So it isn't tied to loops, and the caller decides what
+/// skipping means by matching on it.
+/// A message can follow, printed when it's `Break`, the same as with [`unwrap_continue!`].
+/// # Examples
+/// ```
+/// # use loop_unwrap::check;
+/// use std::ops::ControlFlow;
+///
+/// fn describe(input: &str) -> String {
+///     match check!(input.parse::<i32>(), "Couldn't parse {:?}", input) {
+///         ControlFlow::Continue(n) => format!("got {}", n),
+///         ControlFlow::Break(()) => String::from("skipped"),
+///     }
+/// }
+///
+/// assert_eq!(describe("7"), "got 7");
+/// assert_eq!(describe("seven"), "skipped");
+/// assert_eq!(check!(None::<i32>), ControlFlow::Break(()));
+/// assert_eq!(check!(Some(3)), ControlFlow::Continue(3));
+/// ```
+#[macro_export]
+macro_rules! check {
+    ($x:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => ::core::ops::ControlFlow::Continue(v),
+            None => ::core::ops::ControlFlow::Break(()),
+        }
+    };
+    ($x:expr, $($msg:tt)+) => {
+        ::loop_unwrap::__loop_unwrap_core!(
+            ::loop_unwrap::ToOption::to_option($x).map(::core::ops::ControlFlow::Continue),
+            { ::core::ops::ControlFlow::Break(()) },
+            $($msg)+
+        )
+    };
+}
+
+/// Works like [`unwrap_continue!`] inside the closure of an iterator adapter like
+/// [`try_for_each`](Iterator::try_for_each), where `continue` isn't allowed: if it's an Err or
+/// None, it does `return ControlFlow::Continue(())` from the closure, moving on to the next item.
+/// Otherwise, it evaluates to the unwrapped value.
+/// Returning `Continue(())` means the closure has to return `ControlFlow<B, ()>`, as with
+/// `try_for_each`, or [`try_fold`](Iterator::try_fold) keeping its state elsewhere. The
+/// closure can still stop the iteration itself with `ControlFlow::Break`.
+/// A message can follow, printed before returning, the same as with [`unwrap_continue!`].
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue_cf;
+/// use std::ops::ControlFlow;
+///
+/// let mut sum = 0;
+/// let flow = ["1", "two", "3", "stop", "5"].iter().try_for_each(|input| {
+///     if *input == "stop" {
+///         return ControlFlow::Break(*input);
+///     }
+///     sum += unwrap_continue_cf!(input.parse::<i32>(), "Couldn't parse {:?}", input);
+///     ControlFlow::Continue(())
+/// });
+/// assert_eq!(flow, ControlFlow::Break("stop"));
+/// assert_eq!(sum, 4);
+/// ```
+#[macro_export]
+macro_rules! unwrap_continue_cf {
+    ($x:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                return ::core::ops::ControlFlow::Continue(());
+            }
+        }
+    };
+    ($x:expr, $($msg:tt)+) => {
+        ::loop_unwrap::__loop_unwrap_core!(
+            $x,
+            {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                return ::core::ops::ControlFlow::Continue(())
+            },
+            $($msg)+
+        )
+    };
+}
+
+/// Parses the input (anything with a `parse` method, like `&str` or `String`) as the given type,
+/// and works like [`unwrap_continue!`] on the result: a parse error calls `continue` on the loop.
+/// Shorthand for `unwrap_continue!(input.parse::<Type>(), ...)`, so the `let` needs no type
+/// annotation. Everything after the type is passed on to [`unwrap_continue!`], so it takes the
+/// same messages, labels and keyword forms, except for `const`, which only works on an Option.
+/// # Examples
+/// ```
+/// # use loop_unwrap::parse_continue;
+/// let mut sum = 0;
+/// for input in &["1", "two", "3"] {
+///     let n = parse_continue!(input, i32);
+///     sum += n;
+/// }
+/// assert_eq!(sum, 4);
+/// ```
+/// ```
+/// # use loop_unwrap::parse_continue;
+/// let mut total = 0.0;
+/// 'lines: for line in &["1.5 2.5", "3.0 x"] {
+///     for field in line.split(' ') {
+///         total += parse_continue!(field, f64, 'lines, "Skipping the rest of {:?}", line);
+///     }
+/// }
+/// assert_eq!(total, 7.0);
+/// ```
+/// ```
+/// # use loop_unwrap::parse_continue;
+/// let mut bad = Vec::new();
+/// for input in &["1", "two", "3"] {
+///     let _n = parse_continue!(input, u8, else |e| { bad.push(e.to_string()); });
+/// }
+/// assert_eq!(bad, vec!["invalid digit found in string"]);
+/// ```
+#[macro_export]
+macro_rules! parse_continue {
+    ($input:expr, $ty:ty) => {
+        ::loop_unwrap::unwrap_continue!(($input).parse::<$ty>())
+    };
+    ($input:expr, $ty:ty, $($rest:tt)+) => {
+        ::loop_unwrap::unwrap_continue!(($input).parse::<$ty>(), $($rest)+)
+    };
+}
+
+/// Receives from an [`mpsc::Receiver`](std::sync::mpsc::Receiver), breaking the loop once all
+/// the senders are gone, and evaluating to the message otherwise.
+/// Given the receiver itself, it blocks on `recv()` (the receiver is only borrowed). Given the
+/// result of `try_recv()` or `recv_timeout()`, an empty channel or a timeout calls `continue`
+/// instead, and only a disconnected channel calls `break`.
+/// Takes an optional [message and label](crate#messages-and-labels), the message printed when
+/// breaking.
+/// Only available with the `std` feature.
+/// # Examples
+/// ```
+/// # use loop_unwrap::recv_break;
+/// use std::sync::mpsc;
+/// use std::thread;
+///
+/// let (tx, rx) = mpsc::channel();
+/// thread::spawn(move || {
+///     for n in 1..=3 {
+///         tx.send(n).unwrap();
+///     }
+/// });
+/// let mut sum = 0;
+/// loop {
+///     sum += recv_break!(rx, "Senders are gone");
+/// }
+/// assert_eq!(sum, 6);
+/// ```
+/// ```
+/// # use loop_unwrap::recv_break;
+/// use std::sync::mpsc;
+///
+/// let (tx, rx) = mpsc::channel();
+/// tx.send("ping").unwrap();
+/// let mut tx = Some(tx);
+/// let mut polls = 0;
+/// let mut received = Vec::new();
+/// loop {
+///     polls += 1;
+///     if polls == 3 {
+///         drop(tx.take()); // the only sender is gone
+///     }
+///     received.push(recv_break!(rx.try_recv()));
+/// }
+/// assert_eq!(received, vec!["ping"]);
+/// assert_eq!(polls, 3); // "ping", then `Empty`, then `Disconnected`
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! recv_break {
+    ($rx:expr) => {
+        match {
+            #[allow(unused_imports)]
+            use ::loop_unwrap::__private::RecvStep as _;
+            ($rx).recv_step()
+        } {
+            ::core::ops::ControlFlow::Continue(Some(v)) => v,
+            ::core::ops::ControlFlow::Continue(None) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+            ::core::ops::ControlFlow::Break(()) => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break;
+            }
+        }
+    };
+    ($rx:expr, $label:lifetime) => {
+        match {
+            #[allow(unused_imports)]
+            use ::loop_unwrap::__private::RecvStep as _;
+            ($rx).recv_step()
+        } {
+            ::core::ops::ControlFlow::Continue(Some(v)) => v,
+            ::core::ops::ControlFlow::Continue(None) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+            ::core::ops::ControlFlow::Break(()) => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label;
+            }
+        }
+    };
+    ($rx:expr, $label:lifetime, $err_msg:expr) => {
+        match {
+            #[allow(unused_imports)]
+            use ::loop_unwrap::__private::RecvStep as _;
+            ($rx).recv_step()
+        } {
+            ::core::ops::ControlFlow::Continue(Some(v)) => v,
+            ::core::ops::ControlFlow::Continue(None) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+            ::core::ops::ControlFlow::Break(()) => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label;
+            }
+        }
+    };
+    ($rx:expr, $label:lifetime, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match {
+            #[allow(unused_imports)]
+            use ::loop_unwrap::__private::RecvStep as _;
+            ($rx).recv_step()
+        } {
+            ::core::ops::ControlFlow::Continue(Some(v)) => v,
+            ::core::ops::ControlFlow::Continue(None) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+            ::core::ops::ControlFlow::Break(()) => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!($fmt, $($args),+));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label;
+            }
+        }
+    };
+    ($rx:expr, $err_msg:expr) => {
+        match {
+            #[allow(unused_imports)]
+            use ::loop_unwrap::__private::RecvStep as _;
+            ($rx).recv_step()
+        } {
+            ::core::ops::ControlFlow::Continue(Some(v)) => v,
+            ::core::ops::ControlFlow::Continue(None) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+            ::core::ops::ControlFlow::Break(()) => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break;
+            }
+        }
+    };
+    ($rx:expr, $err_msg:expr, $label:lifetime) => {
+        match {
+            #[allow(unused_imports)]
+            use ::loop_unwrap::__private::RecvStep as _;
+            ($rx).recv_step()
+        } {
+            ::core::ops::ControlFlow::Continue(Some(v)) => v,
+            ::core::ops::ControlFlow::Continue(None) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+            ::core::ops::ControlFlow::Break(()) => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label;
+            }
+        }
+    };
+    ($rx:expr, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match {
+            #[allow(unused_imports)]
+            use ::loop_unwrap::__private::RecvStep as _;
+            ($rx).recv_step()
+        } {
+            ::core::ops::ControlFlow::Continue(Some(v)) => v,
+            ::core::ops::ControlFlow::Continue(None) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+            ::core::ops::ControlFlow::Break(()) => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!($fmt, $($args),+));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break;
+            }
+        }
+    };
+}
+
+/// Checks the deadline (a [`std::time::Instant`]) first, and once it has passed, calls `break`
+/// on the loop, printing the message if provided. Otherwise, it works like [`unwrap_continue!`]
+/// without a message: if it's an Err or None, it calls `continue` on the loop.
+/// The expression isn't evaluated once the deadline has passed.
+/// If loop label is provided, the specified loop is break;-ed or continued.
+/// Only available with the `std` feature.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_break_timeout;
+/// use std::time::{Duration, Instant};
+///
+/// let deadline = Instant::now() + Duration::from_millis(10);
+/// let mut polls = 0;
+/// loop {
+///     polls += 1;
+///     let _ready: () = unwrap_break_timeout!(None, deadline: deadline, "Timed out waiting");
+///     unreachable!();
+/// }
+/// assert!(Instant::now() >= deadline);
+/// assert!(polls > 0);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_break_timeout;
+/// use std::time::{Duration, Instant};
+///
+/// let deadline = Instant::now() + Duration::from_secs(60);
+/// let mut replies = vec![None, Some("pong")].into_iter();
+/// let mut reply = None;
+/// while reply.is_none() {
+///     reply = Some(unwrap_break_timeout!(replies.next().unwrap(), deadline: deadline));
+/// }
+/// assert_eq!(reply, Some("pong"));
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! unwrap_break_timeout {
+    ($x:expr, deadline: $deadline:expr) => {{
+        if ::std::time::Instant::now() >= $deadline {
+            ::loop_unwrap::__loop_unwrap_count_break();
+            break;
+        }
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    }};
+    ($x:expr, $label:lifetime, deadline: $deadline:expr) => {{
+        if ::std::time::Instant::now() >= $deadline {
+            ::loop_unwrap::__loop_unwrap_count_break();
+            break $label;
+        }
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    }};
+    ($x:expr, $label:lifetime, deadline: $deadline:expr, $err_msg:expr) => {{
+        if ::std::time::Instant::now() >= $deadline {
+            ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+            ::loop_unwrap::__loop_unwrap_count_break();
+            break $label;
+        }
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    }};
+    ($x:expr, $label:lifetime, deadline: $deadline:expr, $fmt:literal, $($args:expr),+ $(,)?) => {{
+        if ::std::time::Instant::now() >= $deadline {
+            ::loop_unwrap::__loop_unwrap_emit(format_args!($fmt, $($args),+));
+            ::loop_unwrap::__loop_unwrap_count_break();
+            break $label;
+        }
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    }};
+    ($x:expr, deadline: $deadline:expr, $err_msg:expr) => {{
+        if ::std::time::Instant::now() >= $deadline {
+            ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+            ::loop_unwrap::__loop_unwrap_count_break();
+            break;
+        }
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    }};
+    ($x:expr, deadline: $deadline:expr, $err_msg:expr, $label:lifetime) => {{
+        if ::std::time::Instant::now() >= $deadline {
+            ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+            ::loop_unwrap::__loop_unwrap_count_break();
+            break $label;
+        }
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    }};
+    ($x:expr, deadline: $deadline:expr, $fmt:literal, $($args:expr),+ $(,)?) => {{
+        if ::std::time::Instant::now() >= $deadline {
+            ::loop_unwrap::__loop_unwrap_emit(format_args!($fmt, $($args),+));
+            ::loop_unwrap::__loop_unwrap_count_break();
+            break;
+        }
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    }};
+}
+
+/// Reads a line from stdin into `buf` (a `String`, cleared first), and evaluates to the line
+/// with surrounding whitespace trimmed, borrowed from `buf`. If reading fails, it calls
+/// `continue` on the loop, and at the end of input it calls `break`, since reading again would
+/// only give empty lines.
+/// If a prompt is provided, it's printed with `print!()` before reading, and stdout is flushed so
+/// it shows up on the same line. If loop label is provided, the specified loop is continued or
+/// break;-ed. Only available with the `std` feature.
+/// # Examples
+/// ```no_run
+/// # use loop_unwrap::{read_line_continue, unwrap_continue};
+/// let mut buf = String::new();
+/// let mut sum = 0;
+/// loop {
+///     let line = read_line_continue!(buf, "Enter a number: ");
+///     let n: i32 = unwrap_continue!(line.parse(), "That's not a number");
+///     sum += n;
+///     println!("Sum so far: {}", sum);
+/// }
+/// println!("Total: {}", sum); // once stdin is closed
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! read_line_continue {
+    ($buf:expr) => {{
+        $buf.clear();
+        match ::std::io::stdin().read_line(&mut $buf) {
+            Ok(0) => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break;
+            }
+            Ok(_) => $buf.trim(),
+            Err(_) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    }};
+    ($buf:expr, $label:lifetime) => {{
+        $buf.clear();
+        match ::std::io::stdin().read_line(&mut $buf) {
+            Ok(0) => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label;
+            }
+            Ok(_) => $buf.trim(),
+            Err(_) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    }};
+    ($buf:expr, $prompt:expr) => {{
+        ::std::print!("{}", $prompt);
+        let _ = ::std::io::Write::flush(&mut ::std::io::stdout());
+        $buf.clear();
+        match ::std::io::stdin().read_line(&mut $buf) {
+            Ok(0) => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break;
+            }
+            Ok(_) => $buf.trim(),
+            Err(_) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    }};
+    ($buf:expr, $label:lifetime, $prompt:expr) => {{
+        ::std::print!("{}", $prompt);
+        let _ = ::std::io::Write::flush(&mut ::std::io::stdout());
+        $buf.clear();
+        match ::std::io::stdin().read_line(&mut $buf) {
+            Ok(0) => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label;
+            }
+            Ok(_) => $buf.trim(),
+            Err(_) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    }};
+}
+
+/// Evaluates the expression, and if it panics, catches the panic with
+/// [`std::panic::catch_unwind`] and calls `continue` on the loop instead of unwinding further.
+/// For loops calling code that may panic on bad input, like a third-party parser.
+/// Evaluates to the value of the expression as is, so an Option or Result it returns isn't
+/// unwrapped: pass it to [`unwrap_continue!`] for that.
+/// Takes an optional [message and label](crate#messages-and-labels).
+/// Only available with the `std` feature.
+///
+/// The expression runs in a closure, so it can't `continue`, `break`, `return` or use `?`
+/// itself. The closure is wrapped in [`AssertUnwindSafe`](std::panic::AssertUnwindSafe), so it
+/// compiles even when it borrows something mutably: it's up to the caller to make sure what the
+/// expression was modifying when it panicked isn't relied on afterwards, as it can be left
+/// half-updated. A `Mutex` locked by the expression is poisoned as usual.
+/// Panics are still reported by the panic hook, printing to stderr by default; install another
+/// one with [`std::panic::set_hook`] to silence them. With `panic = "abort"` in the profile,
+/// nothing can be caught and the program aborts.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue_catch;
+/// fn third_party_parse(input: &str) -> u8 {
+///     input.parse().expect("not a number")
+/// }
+///
+/// # std::panic::set_hook(Box::new(|_| {}));
+/// let mut parsed = Vec::new();
+/// for input in &["1", "two", "3"] {
+///     parsed.push(unwrap_continue_catch!(third_party_parse(input), "recovered from panic"));
+///     // "recovered from panic" is printed for "two", after the panic message
+/// }
+/// assert_eq!(parsed, vec![1, 3]);
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! unwrap_continue_catch {
+    ($x:expr) => {
+        match ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| $x)) {
+            Ok(v) => v,
+            Err(_) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime) => {
+        match ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| $x)) {
+            Ok(v) => v,
+            Err(_) => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| $x)) {
+            Ok(v) => v,
+            Err(_) => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!($fmt, $($args),+));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, $err_msg:expr) => {
+        match ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| $x)) {
+            Ok(v) => v,
+            Err(_) => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| $x)) {
+            Ok(v) => v,
+            Err(_) => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!($fmt, $($args),+));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr, $label:lifetime) => {
+        match ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| $x)) {
+            Ok(v) => v,
+            Err(_) => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr) => {
+        match ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| $x)) {
+            Ok(v) => v,
+            Err(_) => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+}
+
+/// The opposite of [`unwrap_break_err!`]: if it's an Ok or Some, it breaks the loop with
+/// `Ok(value)`. Otherwise, it does nothing, and the rest of the loop body runs.
+/// If loop label is provided, the specified loop will be break;-ed.
+/// Like `unwrap_break_err!`, this only works in `loop`, since it breaks with a value.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_break_ok;
+/// let mut attempts = vec!["x", "", "42", "7"].into_iter();
+/// let parsed: Result<i32, &str> = loop {
+///     let input = match attempts.next() {
+///         Some(input) => input,
+///         None => break Err("no valid input"),
+///     };
+///     unwrap_break_ok!(input.parse::<i32>());
+///     println!("{:?} isn't a number, trying the next one", input);
+/// };
+/// assert_eq!(parsed, Ok(42));
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_break_ok;
+/// let grid = [["a", "b"], ["c", "7"]];
+/// let found: Result<u32, ()> = 'search: loop {
+///     for row in &grid {
+///         for cell in row {
+///             unwrap_break_ok!(cell.parse::<u32>(), 'search);
+///         }
+///     }
+///     break Err(());
+/// };
+/// assert_eq!(found, Ok(7));
+/// ```
+#[macro_export]
+macro_rules! unwrap_break_ok {
+    ($x:expr) => {
+        if let Some(v) = ::loop_unwrap::ToOption::to_option($x) {
+            ::loop_unwrap::__loop_unwrap_count_break();
+            break Ok(v);
+        }
+    };
+    ($x:expr, $label:lifetime) => {
+        if let Some(v) = ::loop_unwrap::ToOption::to_option($x) {
+            ::loop_unwrap::__loop_unwrap_count_break();
+            break $label Ok(v);
+        }
+    };
+}
+
+/// Breaks the loop with `Ok(value)` if it's an Ok or Some, and calls `continue` on the loop
+/// otherwise, so the loop evaluates to `Ok` of the first success. The loop has to end some other
+/// way when nothing succeeds, like `break Err(...)` once the inputs run out.
+/// If loop label is provided, the specified loop will be break;-ed or continued.
+/// Like [`unwrap_break_ok!`], this only works in `loop`, since it breaks with a value.
+/// # Examples
+/// ```
+/// # use loop_unwrap::break_ok_or_continue;
+/// let mut mirrors = vec!["timeout", "8080", "9090"].into_iter();
+/// let port: Result<u16, &str> = loop {
+///     let Some(mirror) = mirrors.next() else {
+///         break Err("no mirror answered");
+///     };
+///     break_ok_or_continue!(mirror.parse::<u16>());
+/// };
+/// assert_eq!(port, Ok(8080));
+///
+/// let mut inputs = vec![None, None].into_iter();
+/// let first: Result<i32, ()> = 'outer: loop {
+///     let Some(input) = inputs.next() else {
+///         break Err(());
+///     };
+///     loop {
+///         break_ok_or_continue!(input, 'outer);
+///     }
+/// };
+/// assert_eq!(first, Err(()));
+/// ```
+#[macro_export]
+macro_rules! break_ok_or_continue {
+    ($x:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break Ok(v);
+            }
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => {
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label Ok(v);
+            }
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+}
+
+/// Works like `.unwrap`, if it's an Err or None, it breaks the loop with an error built from
+/// the message, instead of the original error: `break Err(E::from(message))`, where `E` is the
+/// error type of the loop's `Result`. So `E` has to implement `From<&str>` for a message like
+/// `"message"`, or `From<String>` for a format string followed by its arguments.
+/// Nothing is printed. Supports loop labels, and like [`unwrap_break_err!`], only works in `loop`.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_break_with_msg;
+/// #[derive(Debug, PartialEq)]
+/// struct ConfigError(String);
+///
+/// impl From<&str> for ConfigError {
+///     fn from(message: &str) -> Self {
+///         ConfigError(message.to_string())
+///     }
+/// }
+///
+/// impl From<String> for ConfigError {
+///     fn from(message: String) -> Self {
+///         ConfigError(message)
+///     }
+/// }
+///
+/// let mut lines = vec!["8080", "eighty"].into_iter();
+/// let result: Result<(), ConfigError> = loop {
+///     let line = unwrap_break_with_msg!(lines.next(), "missing port");
+///     let _port: u16 = unwrap_break_with_msg!(line.parse(), "bad port {:?}", line);
+/// };
+/// assert_eq!(result, Err(ConfigError("bad port \"eighty\"".to_string())));
+///
+/// let result: Result<(), ConfigError> = loop {
+///     unwrap_break_with_msg!(lines.next(), "missing port");
+/// };
+/// assert_eq!(result, Err(ConfigError("missing port".to_string())));
+/// ```
+#[macro_export]
+macro_rules! unwrap_break_with_msg {
+    ($x:expr, $label:lifetime, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err(::core::convert::From::from($err_msg));
+            }
+        }
+    };
+    ($x:expr, $label:lifetime, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err(::core::convert::From::from(::std::format!($fmt, $($args),+)));
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break Err(::core::convert::From::from($err_msg));
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break $label Err(::core::convert::From::from($err_msg));
+            }
+        }
+    };
+    ($x:expr, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count_err_break();
+                break Err(::core::convert::From::from(::std::format!($fmt, $($args),+)));
+            }
+        }
+    };
+}
+
+/// Works like `.unwrap`, if it's an Err or None, it returns the exit code given as the second
+/// argument from the enclosing function, for CLI tools that turn it into the process's exit status.
+/// Prints a [message](crate#messages-and-labels) if provided, to stderr by default, then flushes
+/// the output so nothing is lost if the process exits right after.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_exit;
+/// fn run(args: &[&str]) -> i32 {
+///     let mut total = 0;
+///     for arg in args {
+///         total += unwrap_exit!(arg.parse::<i32>(), 2, "error: {:?} is not a number", arg);
+///         // "error: "x" is not a number" is printed to stderr with an `eprintln!()`
+///     }
+///     println!("{}", total);
+///     0
+/// }
+/// assert_eq!(run(&["1", "2"]), 0);
+/// assert_eq!(run(&["1", "x"]), 2);
+/// ```
+/// ```
+/// # use loop_unwrap::unwrap_exit;
+/// use std::process::ExitCode;
+///
+/// fn main() -> ExitCode {
+///     let name = unwrap_exit!(std::env::args().next(), ExitCode::FAILURE, "no program name");
+///     println!("{}", name);
+///     ExitCode::SUCCESS
+/// }
+/// # main();
+/// ```
+#[macro_export]
+macro_rules! unwrap_exit {
+    ($x:expr, $code:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_flush();
+                return $code;
+            }
+        }
+    };
+    ($x:expr, $code:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit_stderr(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_flush();
+                return $code;
+            }
+        }
+    };
+    ($x:expr, $code:expr, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit_stderr(format_args!($fmt, $($args),+));
+                ::loop_unwrap::__loop_unwrap_flush();
+                return $code;
+            }
+        }
+    };
+}
+
+/// Same as [`unwrap_continue!`] with a message, but prints it with `{:?}` instead of `{}`, so types
+/// implementing `Debug` but not `Display` (like most error enums) can be used as messages.
+/// The same can be done with [`unwrap_continue!`] and a format string:
+/// `unwrap_continue!(x, "{:?}", message)`.
+/// A [label](crate#messages-and-labels) can be provided before or after the message.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue_dbg;
+/// #[derive(Debug)]
+/// enum Problem {
+///     NotANumber(&'static str),
+/// }
+///
+/// let mut sum = 0;
+/// for input in &["1", "two", "3"] {
+///     sum += unwrap_continue_dbg!(input.parse::<i32>(), Problem::NotANumber(input));
+///     // "NotANumber("two")" is printed in console with a `println!()`
+/// }
+/// assert_eq!(sum, 4);
+/// ```
+#[macro_export]
+macro_rules! unwrap_continue_dbg {
+    ($x:expr, $label:lifetime, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{:?}", $err_msg));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{:?}", $err_msg));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue;
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{:?}", $err_msg));
+                ::loop_unwrap::__loop_unwrap_count_continue();
+                continue $label;
+            }
+        }
+    };
+}
+
+/// Same as [`unwrap_break!`] with a message, but prints it with `{:?}` instead of `{}`, so types
+/// implementing `Debug` but not `Display` (like most error enums) can be used as messages.
+/// The same can be done with [`unwrap_break!`] and a format string:
+/// `unwrap_break!(x, "{:?}", message)`.
+/// A [label](crate#messages-and-labels) can be provided before or after the message.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_break_dbg;
+/// #[derive(Debug)]
+/// enum Problem {
+///     NotANumber(&'static str),
+/// }
+///
+/// let mut sum = 0;
+/// 'main: for line in &["1 2", "3 x", "5 6"] {
+///     for input in line.split(' ') {
+///         sum += unwrap_break_dbg!(input.parse::<i32>(), 'main, Problem::NotANumber("x"));
+///     }
+/// }
+/// assert_eq!(sum, 6);
+/// ```
+#[macro_export]
+macro_rules! unwrap_break_dbg {
+    ($x:expr, $label:lifetime, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{:?}", $err_msg));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label;
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{:?}", $err_msg));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break;
+            }
+        }
+    };
+    ($x:expr, $err_msg:expr, $label:lifetime) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_emit(format_args!("{:?}", $err_msg));
+                ::loop_unwrap::__loop_unwrap_count_break();
+                break $label;
+            }
+        }
+    };
+}
+
+/// Same as [`unwrap_break!`], but prints the message to stderr with `eprintln!()` instead of
+/// stdout by default, which keeps diagnostics out of the program's normal output.
+/// Evaluates to the same value as [`unwrap_break!`], and takes a label and a message the same
+/// way, but none of its keyword forms, like `with:` or `on_err:`.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_break_eprintln;
+/// let mut sum = 0;
+/// 'main: for input in &["1", "two", "3"] {
+///     sum += unwrap_break_eprintln!(input.parse::<i32>());
+///     sum += unwrap_break_eprintln!(input.parse::<i32>(), 'main);
+///     sum += unwrap_break_eprintln!(input.parse::<i32>(), "Please Enter a Number!");
+///     // "Please Enter a Number!" is printed to stderr with an `eprintln!()`
+///     sum += unwrap_break_eprintln!(input.parse::<i32>(), "Please Enter a Number!", 'main);
+///     sum += unwrap_break_eprintln!(input.parse::<i32>(), 'main, "Please Enter a Number!");
+///     sum += unwrap_break_eprintln!(input.parse::<i32>(), "Couldn't parse {:?}", input);
+///     sum += unwrap_break_eprintln!(input.parse::<i32>(), 'main, "Couldn't parse {:?}", input);
+/// }
+/// assert_eq!(sum, 7);
+/// ```
+#[macro_export]
+macro_rules! unwrap_break_eprintln {
+    ($x:expr $(, $($rest:tt)+)?) => {
+        ::loop_unwrap::__loop_unwrap_labelled!(@__loop_unwrap_emit_stderr break, $x $(, $($rest)+)?)
+    };
+}
+
+/// Same as [`unwrap_continue_err!`], but prints the message to stderr with `eprintln!()` instead of
+/// stdout by default, which keeps diagnostics out of the program's normal output.
+/// Works only on Result, evaluates to the same value as [`unwrap_continue_err!`], and takes a
+/// label and a message the same way, but none of its keyword forms, like `else |e|`.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue_err_eprintln;
+/// let mut sum = 0;
+/// 'main: for input in &["1", "two", "3"] {
+///     sum += unwrap_continue_err_eprintln!(input.parse::<i32>());
+///     sum += unwrap_continue_err_eprintln!(input.parse::<i32>(), 'main);
+///     sum += unwrap_continue_err_eprintln!(input.parse::<i32>(), "Please Enter a Number!");
+///     // "Please Enter a Number!" is printed to stderr with an `eprintln!()`
+///     sum += unwrap_continue_err_eprintln!(input.parse::<i32>(), "Please Enter a Number!", 'main);
+///     sum += unwrap_continue_err_eprintln!(input.parse::<i32>(), 'main, "Please Enter a Number!");
+///     sum += unwrap_continue_err_eprintln!(input.parse::<i32>(), "Couldn't parse {:?}", input);
+///     sum += unwrap_continue_err_eprintln!(input.parse::<i32>(), 'main, "Couldn't parse {:?}", input);
+/// }
+/// assert_eq!(sum, 28);
+/// ```
+#[macro_export]
+macro_rules! unwrap_continue_err_eprintln {
+    ($x:expr $(, $($rest:tt)+)?) => {
+        ::loop_unwrap::__loop_unwrap_labelled!(
+            @__loop_unwrap_emit_stderr continue,
+            match $x {
+                Ok(v) => Some(v),
+                Err(_) => None,
+            }
+            $(, $($rest)+)?
+        )
+    };
+}
+
+/// The skeleton the other macros share: unwraps the value with [`ToOption`], and on an Err or
+/// None prints the message if provided, then evaluates `diverge`, which is given as a block like
+/// `{ continue 'outer }` and has to leave the enclosing code (`continue`, `break`, `return`...).
+/// It's meant for writing your own variants on top of it, like a `continue` that also counts:
+/// ```
+/// # use loop_unwrap::__loop_unwrap_core;
+/// macro_rules! continue_counted {
+///     ($x:expr, $skipped:expr) => {
+///         loop_unwrap::__loop_unwrap_core!($x, { $skipped += 1; continue; })
+///     };
+/// }
+///
+/// let mut skipped = 0;
+/// let mut sum = 0;
+/// for input in &["1", "two", "3"] {
+///     sum += continue_counted!(input.parse::<i32>(), skipped);
+/// }
+/// assert_eq!((sum, skipped), (4, 1));
+/// ```
+/// With the `metrics` feature, a `diverge` starting with `continue` or `break` is counted like
+/// the macros' own.
+/// The name starts with underscores as it's not covered by semver: the arms can change in any
+/// release, so pin the version when building on it.
+#[macro_export]
+macro_rules! __loop_unwrap_core {
+    (@$emit:ident $x:expr, $diverge:tt) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::__loop_unwrap_count!($diverge);
+                $diverge
+            }
+        }
+    };
+    (@$emit:ident $x:expr, $diverge:tt, $fmt:literal, $($args:expr),+ $(,)?) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::$emit(format_args!($fmt, $($args),+));
+                ::loop_unwrap::__loop_unwrap_count!($diverge);
+                $diverge
+            }
+        }
+    };
+    (@$emit:ident $x:expr, $diverge:tt, $err_msg:expr) => {
+        match ::loop_unwrap::ToOption::to_option($x) {
+            Some(v) => v,
+            None => {
+                ::loop_unwrap::$emit(format_args!("{}", ::loop_unwrap::__loop_unwrap_message!($err_msg)));
+                ::loop_unwrap::__loop_unwrap_count!($diverge);
+                $diverge
+            }
+        }
+    };
+    ($x:expr, $diverge:tt $(, $($msg:tt)+)?) => {
+        ::loop_unwrap::__loop_unwrap_core!(@__loop_unwrap_emit $x, $diverge $(, $($msg)+)?)
+    };
+}
+
+/// The label and message grammar shared by the macros built on [`__loop_unwrap_core!`]: `$kw`
+/// is `continue` or `break`, and the label can come before or after a message, but only before
+/// a format string. `@emit` picks the function printing the message, as for the core macro.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __loop_unwrap_labelled {
+    (@$emit:ident $kw:tt, $x:expr) => {
+        ::loop_unwrap::__loop_unwrap_core!(@$emit $x, { $kw })
+    };
+    (@$emit:ident $kw:tt, $x:expr, $label:lifetime) => {
+        ::loop_unwrap::__loop_unwrap_core!(@$emit $x, { $kw $label })
+    };
+    (@$emit:ident $kw:tt, $x:expr, $label:lifetime, $($msg:tt)+) => {
+        ::loop_unwrap::__loop_unwrap_core!(@$emit $x, { $kw $label }, $($msg)+)
+    };
+    (@$emit:ident $kw:tt, $x:expr, $err_msg:expr, $label:lifetime) => {
+        ::loop_unwrap::__loop_unwrap_core!(@$emit $x, { $kw $label }, $err_msg)
+    };
+    (@$emit:ident $kw:tt, $x:expr, $($msg:tt)+) => {
+        ::loop_unwrap::__loop_unwrap_core!(@$emit $x, { $kw }, $($msg)+)
+    };
+    ($kw:tt, $($rest:tt)+) => {
+        ::loop_unwrap::__loop_unwrap_labelled!(@__loop_unwrap_emit $kw, $($rest)+)
+    };
+}
+
+/// Counts a `diverge` of [`__loop_unwrap_core!`] starting with `continue` or `break` for the
+/// `metrics` feature, and ignores any other.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __loop_unwrap_count {
+    ({ continue $($rest:tt)* }) => {
+        ::loop_unwrap::__loop_unwrap_count_continue()
+    };
+    ({ break $($label:lifetime)? Err $($rest:tt)* }) => {
+        ::loop_unwrap::__loop_unwrap_count_err_break()
+    };
+    ({ break $($rest:tt)* }) => {
+        ::loop_unwrap::__loop_unwrap_count_break()
+    };
+    ($diverge:tt) => {};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __loop_unwrap_message {
+    ($msg:expr) => {{
+        #[allow(unused_imports)]
+        use ::loop_unwrap::__private::{ViaDisplay as _, ViaMessage as _};
+        (&::loop_unwrap::__private::MessageArg::new($msg)).message_text()
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __loop_unwrap_is_skip {
+    ($e:expr) => {{
+        #[allow(unused_imports)]
+        use ::loop_unwrap::__private::{SkipByDefault as _, ViaSkipError as _};
+        (&::loop_unwrap::__private::Classified(&$e)).is_skip()
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __loop_unwrap_error_field {
+    ($e:expr) => {{
+        #[allow(unused_imports)]
+        use ::loop_unwrap::__private::{DebugField as _, NoField as _};
+        (&::loop_unwrap::__private::ErrorField(&$e)).debug_field()
+    }};
+}
+
+#[cfg(feature = "anyhow")]
+#[doc(hidden)]
+pub fn __loop_unwrap_report<E: Into<anyhow::Error>>(error: E) -> anyhow::Error {
+    error.into()
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn __loop_unwrap_debug_panic(debug: bool) {
+    if cfg!(feature = "panic-in-debug") && debug {
+        panic!("unwrap_continue! got an Err or None, and the panic-in-debug feature is enabled");
+    }
+}
+
+#[doc(hidden)]
+#[inline]
+pub fn __loop_unwrap_count_continue() {
+    #[cfg(feature = "metrics")]
+    metrics::count(&metrics::CONTINUES);
+}
+
+#[doc(hidden)]
+#[inline]
+pub fn __loop_unwrap_count_break() {
+    #[cfg(feature = "metrics")]
+    metrics::count(&metrics::BREAKS);
+}
+
+#[doc(hidden)]
+#[inline]
+pub fn __loop_unwrap_count_err_break() {
+    #[cfg(feature = "metrics")]
+    metrics::count(&metrics::ERR_BREAKS);
+}
+
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub fn __loop_unwrap_backoff<A>(
+    base: std::time::Duration,
+    attempts: A,
+    cap: std::time::Duration,
+) -> std::time::Duration
+where
+    u32: core::convert::TryFrom<A>,
+{
+    let attempts = <u32 as core::convert::TryFrom<A>>::try_from(attempts).unwrap_or(u32::MAX);
+    2u32.checked_pow(attempts.saturating_sub(1))
+        .and_then(|factor| base.checked_mul(factor))
+        .map_or(cap, |backoff| backoff.min(cap))
+}
+
+#[doc(hidden)]
+pub fn __loop_unwrap_map<E, F>(error: E, map: impl FnOnce(E) -> F) -> F {
+    map(error)
+}
+
+#[doc(hidden)]
+pub fn __loop_unwrap_check<T>(value: &T, predicate: impl FnOnce(&T) -> bool) -> bool {
+    predicate(value)
+}
+
+/// A message for the macros to print, turned into its text only when it's printed.
+/// Implemented for `&str`, `String` and closures returning something `Display`, so
+/// `unwrap_continue!(x, || format!(...))` builds the message only when it's needed. The message
+/// arms take any `Display` type as well, printed as is.
+/// Every macro taking a message accepts it, except the `_dbg` ones, which print theirs with
+/// `{:?}`, and [`unwrap_break_with_msg!`], which breaks with its message instead.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// let mut built = 0;
+/// let mut sum = 0;
+/// for input in &["1", "two", "3"] {
+///     let describe = || {
+///         built += 1;
+///         format!("Couldn't parse {:?}", input)
+///     };
+///     sum += unwrap_continue!(input.parse::<i32>(), describe);
+/// }
+/// assert_eq!((sum, built), (4, 1));
+/// ```
+pub trait Message {
+    /// What gets printed.
+    type Text: core::fmt::Display;
+
+    /// Turns the message into the text to print.
+    fn into_text(self) -> Self::Text;
+}
+
+impl<'a> Message for &'a str {
+    type Text = &'a str;
+
+    #[inline]
+    fn into_text(self) -> &'a str {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl Message for String {
+    type Text = String;
+
+    #[inline]
+    fn into_text(self) -> String {
+        self
+    }
+}
+
+impl<F, D> Message for F
+where
+    F: FnOnce() -> D,
+    D: core::fmt::Display,
+{
+    type Text = D;
+
+    #[inline]
+    fn into_text(self) -> D {
+        self()
+    }
+}
+
+/// Converts a value into an Option, `None` meaning the macros skip or break.
+/// Every macro goes through it, so implementing it is how a type gets to be used with them.
+/// It's part of the public API, and can be called directly as well: `to_option` and the impls
+/// below only change in breaking releases.
+///
+/// Implemented for:
+/// - `Option<T>` and `Result<T, E>` (through [`LoopSkip`]), where `Ok` is `Some`.
+/// - `&Option<T>` and `&Result<T, E>`, cloning the value out. [`AsOption`] borrows it instead.
+/// - `Poll<T>`, where `Pending` is `None`.
+/// - `*const T` and `*mut T`, where null is `None`.
+/// - `bool`, where `false` is `None`.
+/// - everything implementing [`LoopSkip`].
+/// ```
+/// use loop_unwrap::ToOption;
+///
+/// let parsed = "5".parse::<i32>();
+/// match (&parsed).to_option() {
+///     Some(n) => assert_eq!(n, 5),
+///     None => unreachable!(),
+/// }
+/// assert_eq!(parsed.to_option(), Some(5));
+/// assert_eq!(None::<i32>.to_option(), None);
+/// ```
+///
+/// There's no blanket impl for everything implementing `Into<Option<T>>`: it would overlap with
+/// the impls for `Option<T>` (through `From<T> for T`) and `Result<T, E>`. For your own enums,
+/// enable the `derive` feature and use `#[derive(ToOption)]`. The variant with a single field
+/// holds the value, or the one marked with `#[to_option(some)]` if there are several.
+/// Types that can't be derived can implement [`LoopSkip`], which implements this.
+/// ```ignore
+/// use loop_unwrap::{unwrap_continue, ToOption};
+///
+/// #[derive(ToOption)]
+/// enum Outcome<T> {
+///     #[to_option(some)]
+///     Done(T),
+///     Skipped,
+///     Failed(String),
+/// }
+///
+/// for outcome in vec![Outcome::Done(1), Outcome::Skipped, Outcome::Failed("nope".into())] {
+///     let n: i32 = unwrap_continue!(outcome);
+/// }
+/// ```
+pub trait ToOption<T> {
+    /// Converts the value, `None` meaning the macros skip or break.
+    fn to_option(self) -> Option<T>;
+
+    /// Like [`to_option`](ToOption::to_option), but panics instead of returning `None` for an
+    /// `Err`, used by [`unwrap_continue_strict!`]. Defaults to `to_option`.
+    #[inline]
+    #[track_caller]
+    fn into_strict_option(self) -> Option<T>
+    where
+        Self: Sized,
+    {
+        self.to_option()
+    }
+}
+
+/// Borrows the value inside an Option-like value, for using it in match logic without
+/// consuming or cloning it.
+/// # Examples
+/// ```
+/// use loop_unwrap::AsOption;
+///
+/// let names: Vec<Result<String, ()>> = vec![Ok("a".to_string()), Err(())];
+/// let lengths: Vec<_> = names.iter().map(|n| n.as_option().map(String::len)).collect();
+/// assert_eq!(lengths, vec![Some(1), None]);
+/// assert_eq!(Some(3).as_option(), Some(&3));
+/// ```
+pub trait AsOption<T> {
+    /// Borrows the value, `None` meaning the macros would skip or break.
+    fn as_option(&self) -> Option<&T>;
+}
+
+impl<T> AsOption<T> for Option<T> {
+    #[inline]
+    fn as_option(&self) -> Option<&T> {
+        self.as_ref()
+    }
+}
+
+impl<T, E> AsOption<T> for Result<T, E> {
+    #[inline]
+    fn as_option(&self) -> Option<&T> {
+        self.as_ref().ok()
+    }
+}
+
+impl<T> AsOption<T> for core::task::Poll<T> {
+    #[inline]
+    fn as_option(&self) -> Option<&T> {
+        match self {
+            core::task::Poll::Ready(v) => Some(v),
+            core::task::Poll::Pending => None,
+        }
+    }
+}
+
+/// Implemented only for Option, for [`unwrap_continue_some!`] to reject everything else.
+pub trait IsOption<T> {
+    /// Returns the Option itself.
+    fn into_option(self) -> Option<T>;
+}
+
+impl<T> IsOption<T> for Option<T> {
+    #[inline]
+    fn into_option(self) -> Option<T> {
+        self
+    }
+}
+
+/// A type the macros can skip or break on, as an alternative to implementing [`ToOption`]
+/// directly: [`ToOption`] is implemented for every type implementing it, mapping a skip to
+/// `None` and anything else to `Some(into_value())`.
+/// # Examples
+/// ```
+/// # use loop_unwrap::{unwrap_continue, LoopSkip};
+/// enum Packet {
+///     Data(Vec<u8>),
+///     KeepAlive,
+/// }
+///
+/// impl LoopSkip for Packet {
+///     type Value = Vec<u8>;
+///
+///     fn is_skip(&self) -> bool {
+///         matches!(self, Packet::KeepAlive)
+///     }
+///
+///     fn into_value(self) -> Vec<u8> {
+///         match self {
+///             Packet::Data(data) => data,
+///             Packet::KeepAlive => unreachable!(),
+///         }
+///     }
+/// }
+///
+/// let packets = vec![Packet::Data(vec![1, 2]), Packet::KeepAlive, Packet::Data(vec![3])];
+/// let mut received = Vec::new();
+/// for packet in packets {
+///     received.extend(unwrap_continue!(packet));
+/// }
+/// assert_eq!(received, vec![1, 2, 3]);
+/// ```
+pub trait LoopSkip {
+    /// The value the macros unwrap to.
+    type Value;
+
+    /// Whether the macros should skip or break on this value.
+    fn is_skip(&self) -> bool;
+
+    /// Unwraps the value. Only called when [`is_skip`](LoopSkip::is_skip) is false, so it may
+    /// panic otherwise.
+    fn into_value(self) -> Self::Value;
+
+    /// Whether this is an error that [`unwrap_continue_strict!`] should panic on instead of
+    /// skipping. Defaults to false.
+    #[inline]
+    fn is_error(&self) -> bool {
+        false
+    }
+}
+
+impl<U: LoopSkip> ToOption<U::Value> for U {
+    #[inline]
+    fn to_option(self) -> Option<U::Value> {
+        if self.is_skip() {
+            None
+        } else {
+            Some(self.into_value())
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn into_strict_option(self) -> Option<U::Value> {
+        if self.is_error() {
+            panic!("called `into_strict_option()` on an `Err` value");
+        }
+        self.to_option()
+    }
+}
+
+impl<T> LoopSkip for Option<T> {
+    type Value = T;
+
+    #[inline]
+    fn is_skip(&self) -> bool {
+        self.is_none()
+    }
+
+    #[inline]
+    fn into_value(self) -> T {
+        self.expect("called `into_value()` on a `None` value")
+    }
+}
+
+/// `Err` is treated like None, `Ok(v)` unwraps to `v`.
+/// This covers the `TryLockResult` from `Mutex::try_lock` and `RwLock::try_read`/`try_write`,
+/// so a polling loop can skip while the lock is held elsewhere. A poisoned lock is an `Err`
+/// too, so it's skipped the same way: to recover the guard instead, match on
+/// `TryLockError::Poisoned`, e.g. with the `else |e|` form of [`unwrap_continue!`].
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// use std::sync::Mutex;
+///
+/// let counter = Mutex::new(0);
+/// let held = counter.lock().unwrap();
+/// let mut attempts = 0;
+/// for _ in 0..3 {
+///     attempts += 1;
+///     let mut guard = unwrap_continue!(counter.try_lock()); // `WouldBlock` while `held` lives
+///     *guard += 1;
+/// }
+/// drop(held);
+/// assert_eq!((attempts, *counter.lock().unwrap()), (3, 0));
+/// ```
+impl<T, E> LoopSkip for Result<T, E> {
+    type Value = T;
+
+    #[inline]
+    fn is_skip(&self) -> bool {
+        self.is_err()
+    }
+
+    #[inline]
+    fn into_value(self) -> T {
+        match self {
+            Ok(v) => v,
+            Err(_) => panic!("called `into_value()` on an `Err` value"),
+        }
+    }
+
+    #[inline]
+    fn is_error(&self) -> bool {
+        self.is_err()
+    }
+}
+
+/// `Pending` is treated like None, `Ready(v)` unwraps to `v`, for loops polling a future by hand.
+/// This is for manual poll loops, like in a hand-written executor or `Future` impl; in async code,
+/// `.await` the future instead.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// use std::task::Poll;
+///
+/// let mut polls = vec![Poll::Pending, Poll::Pending, Poll::Ready(5)].into_iter();
+/// let mut pending = 0;
+/// let value = loop {
+///     let poll = polls.next().unwrap();
+///     if poll.is_pending() {
+///         pending += 1;
+///     }
+///     break unwrap_continue!(poll);
+/// };
+/// assert_eq!(value, 5);
+/// assert_eq!(pending, 2);
+/// ```
+impl<T> LoopSkip for core::task::Poll<T> {
+    type Value = T;
+
+    #[inline]
+    fn is_skip(&self) -> bool {
+        self.is_pending()
+    }
+
+    #[inline]
+    fn into_value(self) -> T {
+        match self {
+            core::task::Poll::Ready(v) => v,
+            core::task::Poll::Pending => panic!("called `into_value()` on a `Pending` value"),
+        }
+    }
+}
+
+/// `()` never skips or breaks, and unwraps to `()`.
+/// That's for calls whose result is only `()` on some paths, like a macro or generic code
+/// handing back the value of a block: the macros accept it without needing to wrap it in
+/// `Some(())`. For a side effect that can fail, `Result<(), E>` and `Option<()>` already work,
+/// used as a statement without a `let`.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// use std::fmt::Write;
+///
+/// let mut out = String::new();
+/// let mut written = 0;
+/// for word in &["a", "b"] {
+///     unwrap_continue!(write!(out, "{} ", word)); // a `fmt::Result`, so `Result<(), fmt::Error>`
+///     unwrap_continue!(()); // never skips
+///     written += 1;
+/// }
+/// assert_eq!((out.as_str(), written), ("a b ", 2));
+/// ```
+impl LoopSkip for () {
+    type Value = ();
+
+    #[inline]
+    fn is_skip(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn into_value(self) {}
+}
+
+/// Clones the value out of a borrowed Option, so the original can stay where it is.
+/// Only the `Some` value is cloned, and only when there is one.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// struct Slot {
+///     name: Option<String>,
+/// }
+///
+/// let slots = vec![Slot { name: Some("a".to_string()) }, Slot { name: None }];
+/// let mut names = Vec::new();
+/// for slot in &slots {
+///     names.push(unwrap_continue!(&slot.name));
+/// }
+/// assert_eq!(names, vec!["a".to_string()]);
+/// assert_eq!(slots[0].name.as_deref(), Some("a")); // still there
+/// ```
+impl<T: Clone> ToOption<T> for &Option<T> {
+    #[inline]
+    fn to_option(self) -> Option<T> {
+        self.clone()
+    }
+}
+
+/// Clones the value out of a borrowed Result, so the original can stay where it is.
+/// Only the `Ok` value is cloned, and only when there is one; the error type needs no `Clone`.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// use std::num::ParseIntError;
+///
+/// struct Parsed {
+///     value: Result<i32, ParseIntError>,
+/// }
+///
+/// let parsed: Vec<_> = ["1", "two", "3"].iter().map(|s| Parsed { value: s.parse() }).collect();
+/// let mut sum = 0;
+/// for p in &parsed {
+///     sum += unwrap_continue!(&p.value);
+/// }
+/// assert_eq!(sum, 4);
+/// assert!(parsed[1].value.is_err());
+/// ```
+impl<T: Clone, E> ToOption<T> for &Result<T, E> {
+    #[inline]
+    fn to_option(self) -> Option<T> {
+        self.as_ref().ok().cloned()
+    }
+
+    #[inline]
+    #[track_caller]
+    fn into_strict_option(self) -> Option<T> {
+        match self {
+            Ok(v) => Some(v.clone()),
+            Err(_) => panic!("called `into_strict_option()` on an `Err` value"),
+        }
+    }
+}
+
+/// A null pointer is treated like None, any other pointer unwraps to itself, so null results of
+/// FFI calls can be skipped. This only checks for null: the pointer is never dereferenced, and
+/// a non-null pointer may still be dangling or misaligned, so it's as unsafe to use as before.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// use std::ptr;
+///
+/// let values = [1, 2];
+/// let pointers = [&values[0] as *const i32, ptr::null(), &values[1] as *const i32];
+/// let mut sum = 0;
+/// for p in &pointers {
+///     let p = unwrap_continue!(*p, "Got a null pointer");
+///     sum += unsafe { *p };
+/// }
+/// assert_eq!(sum, 3);
+/// ```
+impl<T: ?Sized> ToOption<*const T> for *const T {
+    #[inline]
+    fn to_option(self) -> Option<*const T> {
+        if self.is_null() {
+            None
+        } else {
+            Some(self)
+        }
+    }
+}
+
+/// Same as the impl for `*const T`: null is treated like None, and nothing is dereferenced.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// use std::ptr;
+///
+/// let mut values = [1, 2];
+/// let first = &mut values[0] as *mut i32;
+/// let mut doubled = 0;
+/// for p in vec![ptr::null_mut(), first] {
+///     let p = unwrap_continue!(p);
+///     unsafe { *p *= 2 };
+///     doubled += 1;
+/// }
+/// assert_eq!(doubled, 1);
+/// assert_eq!(values, [2, 2]);
+/// ```
+impl<T: ?Sized> ToOption<*mut T> for *mut T {
+    #[inline]
+    fn to_option(self) -> Option<*mut T> {
+        if self.is_null() {
+            None
+        } else {
+            Some(self)
+        }
+    }
+}
+
+/// `true` unwraps to `()`, `false` is treated like None, so a bool works as a loop guard.
+/// # Examples
+/// ```
+/// # use loop_unwrap::{unwrap_break, unwrap_continue};
+/// let mut evens = Vec::new();
+/// for n in 0..6 {
+///     unwrap_continue!(n % 2 == 0); // the unwrapped value is `()`
+///     evens.push(n);
+/// }
+/// assert_eq!(evens, vec![0, 2, 4]);
+///
+/// let mut below_three = Vec::new();
+/// for n in 0..6 {
+///     unwrap_break!(n < 3, "Reached 3, stopping");
+///     below_three.push(n);
+/// }
+/// assert_eq!(below_three, vec![0, 1, 2]);
+/// ```
+impl ToOption<()> for bool {
+    #[inline]
+    fn to_option(self) -> Option<()> {
+        if self {
+            Some(())
+        } else {
+            None
+        }
+    }
+}
+
+/// Like [`ToOption`], but keeps the error around for the macros that hand it to the caller,
+/// such as `unwrap_continue!(x, |e| message)`.
+/// An Option has no error to keep, so None becomes `Err(())`.
+pub trait ToResultLike<T, E> {
+    fn into_parts(self) -> Result<T, E>;
+}
+
+/// # Examples
+/// ```
+/// # use loop_unwrap::ToResultLike;
+/// assert_eq!(Ok::<i32, &str>(5).into_parts(), Ok(5));
+/// assert_eq!(Err::<i32, &str>("bad").into_parts(), Err("bad"));
+/// ```
+impl<T, E> ToResultLike<T, E> for Result<T, E> {
+    #[inline]
+    fn into_parts(self) -> Result<T, E> {
+        self
+    }
+}
+
+/// # Examples
+/// ```
+/// # use loop_unwrap::{unwrap_continue, ToResultLike};
+/// assert_eq!(Some(5).into_parts(), Ok(5));
+/// assert_eq!(None::<i32>.into_parts(), Err(()));
+///
+/// let mut skipped = 0;
+/// for value in &[Some(1), None, Some(3)] {
+///     unwrap_continue!(*value, else |()| { skipped += 1; });
+/// }
+/// assert_eq!(skipped, 1);
+/// ```
+impl<T> ToResultLike<T, ()> for Option<T> {
+    #[inline]
+    fn into_parts(self) -> Result<T, ()> {
+        self.ok_or(())
+    }
+}
+
+/// # Examples
+/// ```
+/// # use loop_unwrap::ToResultLike;
+/// assert_eq!(true.into_parts(), Ok(()));
+/// assert_eq!(false.into_parts(), Err(()));
+/// ```
+impl ToResultLike<(), ()> for bool {
+    #[inline]
+    fn into_parts(self) -> Result<(), ()> {
+        self.to_option().ok_or(())
+    }
+}
+
+/// Function-style counterpart to the macros, for the cases that don't need `continue` or `break`.
+///
+/// `continue` and `break` only work on loops in the same function body, so no function (or
+/// closure) can call them on its caller's loop: that's why the crate is made of macros. What a
+/// function can do is hand the decision back, which `on_none` does with a closure that produces
+/// the value to use instead.
+///
+/// Implemented for everything implementing [`ToOption`].
+/// # Examples
+/// ```
+/// use loop_unwrap::LoopUnwrap;
+///
+/// let mut failures = 0;
+/// let mut values = Vec::new();
+/// for input in &["1", "two", "3"] {
+///     values.push(input.parse::<i32>().on_none(|| {
+///         failures += 1;
+///         -1
+///     }));
+/// }
+/// assert_eq!(values, vec![1, -1, 3]);
+/// assert_eq!(failures, 1);
+///
+/// assert_eq!(Some(5).on_none(|| 0), 5);
+/// assert_eq!(false.on_none(|| ()), ());
+/// ```
+pub trait LoopUnwrap<T> {
+    /// Unwraps the value, or calls `f` for one on an Err or None.
+    fn on_none(self, f: impl FnOnce() -> T) -> T;
+}
+
+impl<T, U: ToOption<T>> LoopUnwrap<T> for U {
+    #[inline]
+    fn on_none(self, f: impl FnOnce() -> T) -> T {
+        self.to_option().unwrap_or_else(f)
+    }
+}
+
+/// Counts the Errs and Nones passed through [`wrap`](SkipCounter::wrap), to find out after the
+/// loop how many iterations were skipped.
+/// # Examples
+/// ```
+/// # use loop_unwrap::{unwrap_continue, SkipCounter};
+/// let mut skipped = SkipCounter::new();
+/// let mut sum = 0;
+/// for input in &["1", "two", "3", "", "5"] {
+///     sum += unwrap_continue!(skipped.wrap(input.parse::<i32>()));
+/// }
+/// assert_eq!(sum, 9);
+/// assert_eq!(skipped.count(), 2);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SkipCounter {
+    count: usize,
+}
+
+impl SkipCounter {
+    /// Creates a counter starting at 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Converts `x` into an Option, counting it if it's an Err or None.
+    pub fn wrap<T>(&mut self, x: impl ToOption<T>) -> Option<T> {
+        let x = x.to_option();
+        if x.is_none() {
+            self.count += 1;
+        }
+        x
+    }
+
+    /// The number of Errs and Nones passed to [`wrap`](SkipCounter::wrap) so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// Collects the errors passed through [`record`](ErrorAccumulator::record), for validation loops
+/// that should report every failure instead of stopping at the first one.
+/// Once the loop is done, [`into_break`](ErrorAccumulator::into_break) turns them into a single
+/// `Err(Vec<E>)` to break with or return.
+/// Only available with the `std` feature.
+/// # Examples
+/// ```
+/// # use loop_unwrap::{unwrap_continue, ErrorAccumulator};
+/// let mut errors = ErrorAccumulator::new();
+/// let mut ports = Vec::new();
+/// let mut inputs = ["80", "http", "443", "", "8080"].iter();
+/// let outcome: Result<Vec<u16>, _> = loop {
+///     let Some(input) = inputs.next() else {
+///         break if errors.is_empty() { Ok(ports) } else { errors.into_break() };
+///     };
+///     ports.push(unwrap_continue!(errors.record(input.parse::<u16>())));
+/// };
+/// let errors = outcome.unwrap_err();
+/// assert_eq!(errors.len(), 2); // "http" and ""
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorAccumulator<E> {
+    errors: Vec<E>,
+}
+
+#[cfg(feature = "std")]
+impl<E> ErrorAccumulator<E> {
+    /// Creates an accumulator without any errors.
+    pub fn new() -> Self {
+        Self { errors: Vec::new() }
+    }
+
+    /// Converts `x` into an Option, keeping the error if it's an Err (`()` for a None).
+    pub fn record<T>(&mut self, x: impl ToResultLike<T, E>) -> Option<T> {
+        match x.into_parts() {
+            Ok(v) => Some(v),
+            Err(e) => {
+                self.errors.push(e);
+                None
+            }
+        }
+    }
+
+    /// The errors recorded so far, oldest first.
+    pub fn errors(&self) -> &[E] {
+        &self.errors
+    }
+
+    /// Whether no error was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Hands over the recorded errors as `Err(errors)`, to break the loop with.
+    pub fn into_break<T>(self) -> Result<T, Vec<E>> {
+        Err(self.errors)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E> Default for ErrorAccumulator<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tells [`unwrap_continue_classified!`] whether an error is worth skipping, or should be
+/// returned from the function instead. `is_skip` defaults to true, so `impl SkipError for E {}`
+/// makes every `E` skipped, same as for error types not implementing it at all.
+pub trait SkipError {
+    /// Returns true if the loop should skip to the next iteration on this error.
+    fn is_skip(&self) -> bool {
+        true
+    }
+}
+
+/// Collections that can be empty, for [`unwrap_continue_nonempty!`] to skip.
+///
+/// Implemented for `str`, slices and arrays, the std collections with the `std` feature, and
+/// references to any of them.
+/// # Examples
+/// ```
+/// use loop_unwrap::IsEmpty;
+///
+/// assert!(IsEmpty::is_empty(""));
+/// assert!(!IsEmpty::is_empty(&vec![1]));
+/// ```
+pub trait IsEmpty {
+    /// Returns true if there's nothing in the collection.
+    fn is_empty(&self) -> bool;
+}
+
+impl<T: IsEmpty + ?Sized> IsEmpty for &T {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        T::is_empty(self)
+    }
+}
+
+impl<T: IsEmpty + ?Sized> IsEmpty for &mut T {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        T::is_empty(self)
+    }
+}
+
+impl IsEmpty for str {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        str::is_empty(self)
+    }
+}
+
+impl<T> IsEmpty for [T] {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        <[T]>::is_empty(self)
+    }
+}
+
+impl<T, const N: usize> IsEmpty for [T; N] {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        N == 0
+    }
+}
+
+#[cfg(feature = "std")]
+impl IsEmpty for String {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        String::is_empty(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> IsEmpty for Vec<T> {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        Vec::is_empty(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> IsEmpty for std::collections::VecDeque<T> {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        std::collections::VecDeque::is_empty(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> IsEmpty for std::collections::HashMap<K, V, S> {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        std::collections::HashMap::is_empty(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> IsEmpty for std::collections::HashSet<T, S> {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        std::collections::HashSet::is_empty(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V> IsEmpty for std::collections::BTreeMap<K, V> {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        std::collections::BTreeMap::is_empty(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> IsEmpty for std::collections::BTreeSet<T> {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        std::collections::BTreeSet::is_empty(self)
+    }
+}
+
+/// Iterator adapters skipping the Errs and Nones, a functional-style alternative to calling the
+/// macros in a hand-written loop.
+///
+/// Implemented for every iterator; the adapters only work when the items implement
+/// [`ToOption`] or, for [`skip_errors_with`](SkipExt::skip_errors_with), [`ToResultLike`].
+/// # Examples
+/// ```
+/// use loop_unwrap::SkipExt;
+///
+/// let numbers: Vec<i32> = ["1", "two", "3"].iter().map(|s| s.parse()).skip_errors().collect();
+/// assert_eq!(numbers, vec![1, 3]);
+///
+/// let mut errors = Vec::new();
+/// let numbers: Vec<i32> = ["4", "", "x", "5"]
+///     .iter()
+///     .map(|s| s.parse::<i32>())
+///     .skip_errors_with(|e| errors.push(e.to_string()))
+///     .collect();
+/// assert_eq!(numbers, vec![4, 5]);
+/// assert_eq!(errors.len(), 2);
+/// ```
+pub trait SkipExt: Iterator + Sized {
+    /// Yields the values inside the Oks and Somes, skipping the rest, like
+    /// [`unwrap_continue!`] in a `for` loop over the iterator.
+    fn skip_errors<T>(self) -> SkipErrors<Self, T>
+    where
+        Self::Item: ToOption<T>,
+    {
+        SkipErrors {
+            iter: self,
+            value: core::marker::PhantomData,
+        }
+    }
+
+    /// Like [`skip_errors`](SkipExt::skip_errors), but calls `f` with the error of every item it
+    /// skips (`()` for a None), like `unwrap_continue!(x, else |e| { ... })`.
+    fn skip_errors_with<T, E, F>(self, f: F) -> SkipErrorsWith<Self, F, T, E>
+    where
+        Self::Item: ToResultLike<T, E>,
+        F: FnMut(E),
+    {
+        SkipErrorsWith {
+            iter: self,
+            f,
+            parts: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<I: Iterator> SkipExt for I {}
+
+/// Iterator returned by [`SkipExt::skip_errors`].
+#[derive(Debug, Clone)]
+pub struct SkipErrors<I, T> {
+    iter: I,
+    value: core::marker::PhantomData<fn() -> T>,
+}
+
+impl<I, T> Iterator for SkipErrors<I, T>
+where
+    I: Iterator,
+    I::Item: ToOption<T>,
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.iter.by_ref().find_map(ToOption::to_option)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+/// Iterator returned by [`SkipExt::skip_errors_with`].
+#[derive(Clone)]
+pub struct SkipErrorsWith<I, F, T, E> {
+    iter: I,
+    f: F,
+    parts: core::marker::PhantomData<fn() -> (T, E)>,
+}
+
+impl<I, F, T, E> Iterator for SkipErrorsWith<I, F, T, E>
+where
+    I: Iterator,
+    I::Item: ToResultLike<T, E>,
+    F: FnMut(E),
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        for item in self.iter.by_ref() {
+            match item.into_parts() {
+                Ok(v) => return Some(v),
+                Err(e) => (self.f)(e),
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
     }
 }
@@ -0,0 +1,55 @@
+//! Counters of how often the macros skipped or broke out of a loop, behind the `metrics` feature.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// How many times the macros called `continue`, across all threads, including
+/// [`unwrap_continue_cf!`](crate::unwrap_continue_cf) returning `ControlFlow::Continue(())`.
+/// The `const` forms aren't counted, as a `const fn` can't update a static.
+pub static CONTINUES: AtomicUsize = AtomicUsize::new(0);
+/// How many times the macros broke out of a loop other than with `Err`: on an Err or None, on a
+/// success for the ones breaking with the value, like [`unwrap_break_ok!`](crate::unwrap_break_ok),
+/// and on the condition ending a loop, like the deadline of
+/// [`unwrap_break_timeout!`](crate::unwrap_break_timeout) or the outer loop of `escalate:`.
+pub static BREAKS: AtomicUsize = AtomicUsize::new(0);
+/// How many times the macros broke with `Err`, like
+/// [`unwrap_break_err!`](crate::unwrap_break_err) and `unwrap_continue!(x, skip_if: ...)` on an
+/// error it didn't skip.
+pub static ERR_BREAKS: AtomicUsize = AtomicUsize::new(0);
+
+/// A snapshot of the counters, returned by [`stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct UnwrapStats {
+    /// See [`CONTINUES`].
+    pub continues: usize,
+    /// See [`BREAKS`].
+    pub breaks: usize,
+    /// See [`ERR_BREAKS`].
+    pub err_breaks: usize,
+}
+
+/// Reads the counters.
+/// The counters are updated independently, so while other threads are running loops the
+/// snapshot can include a `continue` from one without the break that came right after it.
+/// Set a counter back to 0 with `CONTINUES.store(0, Ordering::Relaxed)`.
+/// # Examples
+/// ```
+/// # use loop_unwrap::unwrap_continue;
+/// let before = loop_unwrap::stats();
+/// for input in &["1", "two", "3"] {
+///     let _n: i32 = unwrap_continue!(input.parse());
+/// }
+/// // other tests can be running loops at the same time
+/// assert!(loop_unwrap::stats().continues > before.continues);
+/// ```
+pub fn stats() -> UnwrapStats {
+    UnwrapStats {
+        continues: CONTINUES.load(Ordering::Relaxed),
+        breaks: BREAKS.load(Ordering::Relaxed),
+        err_breaks: ERR_BREAKS.load(Ordering::Relaxed),
+    }
+}
+
+#[inline]
+pub(crate) fn count(counter: &AtomicUsize) {
+    counter.fetch_add(1, Ordering::Relaxed);
+}
@@ -0,0 +1,462 @@
+#[cfg(feature = "capture")]
+use std::cell::RefCell;
+use std::fmt::{self, Debug};
+use std::io::Write;
+use std::panic::Location;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+static WRITER: Mutex<Option<Box<dyn Write + Send>>> = Mutex::new(None);
+
+fn writer() -> MutexGuard<'static, Option<Box<dyn Write + Send>>> {
+    WRITER.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Sends the messages printed by every macro of this crate to `writer`, instead of stdout/stderr.
+/// Useful when printing would get in the way, e.g. while a TUI owns the terminal.
+/// Each message is written as a single line. Errors returned by the writer are ignored.
+/// An installed writer takes precedence over the `log` and `tracing` features.
+/// # Examples
+/// ```
+/// # use loop_unwrap::{set_unwrap_writer, unwrap_continue};
+/// use std::io::{self, Write};
+/// use std::sync::{Arc, Mutex};
+///
+/// #[derive(Clone, Default)]
+/// struct Captured(Arc<Mutex<Vec<u8>>>);
+///
+/// impl Write for Captured {
+///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+///         self.0.lock().unwrap().write(buf)
+///     }
+///     fn flush(&mut self) -> io::Result<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// let captured = Captured::default();
+/// set_unwrap_writer(Box::new(captured.clone()));
+/// for input in &["1", "two"] {
+///     let _n: i32 = unwrap_continue!(input.parse(), "Couldn't parse {:?}", input);
+/// }
+/// # #[cfg(not(feature = "quiet"))]
+/// assert_eq!(&*captured.0.lock().unwrap(), b"Couldn't parse \"two\"\n");
+/// ```
+pub fn set_unwrap_writer(writer: Box<dyn Write + Send>) {
+    *self::writer() = Some(writer);
+}
+
+/// Removes the writer installed with [`set_unwrap_writer`], returning it.
+/// Messages go back to stdout/stderr afterwards.
+pub fn take_unwrap_writer() -> Option<Box<dyn Write + Send>> {
+    writer().take()
+}
+
+/// Where [`UnwrapConfig::destination`] sends the messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Destination {
+    /// Printed with `println!()`, even by the macros printing to stderr by default.
+    Stdout,
+    /// Printed with `eprintln!()`.
+    Stderr,
+    /// Sent to the `log` facade or as a `tracing` event, as described for those features.
+    #[cfg(any(feature = "log", feature = "tracing"))]
+    Log,
+}
+
+/// How the macros format the messages they print, and where to, installed with [`configure`].
+/// Without a config, messages are printed as given, where each macro prints by default.
+/// A writer installed with [`set_unwrap_writer`] still gets the messages whatever the
+/// destination, formatted with the prefix and location, and so does `capture_messages()`
+/// (`capture` feature), so a config can be checked in tests. Messages passed to a `via:`
+/// callback or written to a `buf:` aren't affected.
+/// # Examples
+/// ```
+/// # use loop_unwrap::{configure, unwrap_continue, Destination, UnwrapConfig};
+/// configure(
+///     UnwrapConfig::new()
+///         .prefix("[importer] ")
+///         .destination(Destination::Stderr)
+///         .location(true),
+/// );
+/// for input in &["1", "two"] {
+///     let _n: i32 = unwrap_continue!(input.parse(), "Couldn't parse {:?}", input);
+///     // prints `[importer] [src/main.rs:10] Couldn't parse "two"` to stderr
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UnwrapConfig {
+    prefix: Option<String>,
+    destination: Option<Destination>,
+    location: bool,
+}
+
+impl UnwrapConfig {
+    /// A config leaving the messages as they are, same as not configuring anything.
+    pub fn new() -> Self {
+        UnwrapConfig::default()
+    }
+
+    /// Puts `prefix` in front of every message, as is, so include the separator, like
+    /// `"[app] "`.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Sends every message to `destination`, instead of where each macro prints by default.
+    pub fn destination(mut self, destination: Destination) -> Self {
+        self.destination = Some(destination);
+        self
+    }
+
+    /// With true, messages start with the file and line of the macro call, as
+    /// `[src/main.rs:12] message`, after the prefix.
+    /// [`unwrap_continue_loc!`](crate::unwrap_continue_loc) already starts its messages with it, so
+    /// they show it twice.
+    pub fn location(mut self, location: bool) -> Self {
+        self.location = location;
+        self
+    }
+}
+
+static CONFIG: Mutex<UnwrapConfig> = Mutex::new(UnwrapConfig {
+    prefix: None,
+    destination: None,
+    location: false,
+});
+
+fn config() -> MutexGuard<'static, UnwrapConfig> {
+    CONFIG.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Installs `config` for the messages printed by every macro of this crate, on every thread,
+/// replacing the previous one. `configure(UnwrapConfig::new())` goes back to the defaults.
+pub fn configure(config: UnwrapConfig) {
+    *self::config() = config;
+}
+
+static DEFAULT_MESSAGE: Mutex<Option<String>> = Mutex::new(None);
+
+fn default_message() -> MutexGuard<'static, Option<String>> {
+    DEFAULT_MESSAGE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Replaces [`DEFAULT_SKIP_MESSAGE`](crate::DEFAULT_SKIP_MESSAGE) as the message printed by
+/// `unwrap_continue!(x, msg: default)`, on every thread, so a program can word it once.
+/// # Examples
+/// ```
+/// # use loop_unwrap::{set_default_message, take_default_message, unwrap_continue};
+/// set_default_message("bad record, skipping");
+/// for input in &["1", "two"] {
+///     let _n: i32 = unwrap_continue!(input.parse(), msg: default);
+///     // "bad record, skipping" is printed for "two"
+/// }
+/// assert_eq!(take_default_message().as_deref(), Some("bad record, skipping"));
+/// ```
+pub fn set_default_message(message: impl Into<String>) {
+    *default_message() = Some(message.into());
+}
+
+/// Removes the message set with [`set_default_message`], returning it.
+/// [`DEFAULT_SKIP_MESSAGE`](crate::DEFAULT_SKIP_MESSAGE) is printed again afterwards.
+pub fn take_default_message() -> Option<String> {
+    default_message().take()
+}
+
+#[cfg(feature = "capture")]
+thread_local! {
+    static CAPTURED: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Starts capturing the messages printed by the macros on the current thread, so a test can
+/// look at them with [`take_messages`] instead of capturing stdout.
+/// While capturing, messages on this thread are only captured: they aren't printed or sent to
+/// the writer installed with [`set_unwrap_writer`]. Other threads aren't affected, so tests
+/// running in parallel don't see each other's messages.
+/// Only available with the `capture` feature.
+/// # Examples
+/// ```
+/// # use loop_unwrap::{capture_messages, take_messages, unwrap_continue};
+/// capture_messages();
+/// for input in &["1", "two"] {
+///     let _n: i32 = unwrap_continue!(input.parse(), "Couldn't parse {:?}", input);
+/// }
+/// # #[cfg(not(feature = "quiet"))]
+/// assert_eq!(take_messages(), vec!["Couldn't parse \"two\""]);
+/// ```
+#[cfg(feature = "capture")]
+pub fn capture_messages() {
+    CAPTURED.with(|captured| {
+        captured.borrow_mut().get_or_insert_with(Vec::new);
+    });
+}
+
+/// Returns the messages captured on the current thread since [`capture_messages`], one per
+/// message, and stops capturing. Empty if messages weren't being captured.
+/// Only available with the `capture` feature.
+#[cfg(feature = "capture")]
+pub fn take_messages() -> Vec<String> {
+    CAPTURED
+        .with(|captured| captured.borrow_mut().take())
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "capture")]
+fn capture(args: fmt::Arguments) -> bool {
+    CAPTURED.with(|captured| match captured.borrow_mut().as_mut() {
+        Some(messages) => {
+            messages.push(args.to_string());
+            true
+        }
+        None => false,
+    })
+}
+
+#[cfg(not(feature = "capture"))]
+fn capture(_args: fmt::Arguments) -> bool {
+    false
+}
+
+#[track_caller]
+fn emit(args: fmt::Arguments, stderr: bool, error: Option<&dyn Debug>) {
+    if cfg!(feature = "quiet") {
+        return;
+    }
+    // cloned so the lock isn't held while a writer runs
+    let config = config().clone();
+    let prefix = config.prefix.as_deref().unwrap_or("");
+    let to = config.destination;
+    if config.location {
+        let location = Location::caller();
+        let (file, line) = (location.file(), location.line());
+        deliver(
+            format_args!("{}[{}:{}] {}", prefix, file, line, args),
+            stderr,
+            to,
+            error,
+        );
+    } else if prefix.is_empty() {
+        deliver(args, stderr, to, error);
+    } else {
+        deliver(format_args!("{}{}", prefix, args), stderr, to, error);
+    }
+}
+
+fn deliver(
+    args: fmt::Arguments,
+    stderr: bool,
+    destination: Option<Destination>,
+    error: Option<&dyn Debug>,
+) {
+    if capture(args) {
+        return;
+    }
+    match writer().as_mut() {
+        Some(w) => {
+            let _ = writeln!(w, "{}", args);
+        }
+        None => match destination {
+            Some(Destination::Stdout) => println!("{}", args),
+            Some(Destination::Stderr) => eprintln!("{}", args),
+            _ => fallback(args, stderr, error),
+        },
+    }
+}
+
+#[cfg(not(any(feature = "log", feature = "tracing")))]
+fn fallback(args: fmt::Arguments, stderr: bool, _error: Option<&dyn Debug>) {
+    if stderr {
+        eprintln!("{}", args);
+    } else {
+        println!("{}", args);
+    }
+}
+
+#[cfg(all(feature = "log", not(feature = "tracing")))]
+fn fallback(args: fmt::Arguments, _stderr: bool, _error: Option<&dyn Debug>) {
+    log::warn!(target: "loop_unwrap", "{}", args);
+}
+
+#[cfg(feature = "tracing")]
+fn fallback(args: fmt::Arguments, _stderr: bool, error: Option<&dyn Debug>) {
+    match error {
+        Some(error) => tracing::warn!(target: "loop_unwrap", error = ?error, message = %args),
+        None => tracing::warn!(target: "loop_unwrap", message = %args),
+    }
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn __loop_unwrap_emit(args: fmt::Arguments) {
+    emit(args, false, None);
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn __loop_unwrap_emit_skip(debug: bool, args: fmt::Arguments) {
+    if cfg!(feature = "panic-in-debug") && debug {
+        panic!("{}", args);
+    }
+    emit(args, false, None);
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn __loop_unwrap_emit_default(debug: bool) {
+    // cloned so the lock isn't held while a writer runs
+    let message = default_message().clone();
+    let message = message.as_deref().unwrap_or(crate::DEFAULT_SKIP_MESSAGE);
+    __loop_unwrap_emit_skip(debug, format_args!("{}", message));
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn __loop_unwrap_emit_stderr(args: fmt::Arguments) {
+    emit(args, true, None);
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn __loop_unwrap_emit_error(args: fmt::Arguments, error: Option<&dyn Debug>) {
+    emit(args, false, error);
+}
+
+#[doc(hidden)]
+pub fn __loop_unwrap_via(sink: &mut dyn FnMut(&str), args: fmt::Arguments) {
+    match args.as_str() {
+        Some(message) => sink(message),
+        None => sink(&args.to_string()),
+    }
+}
+
+#[doc(hidden)]
+pub fn __loop_unwrap_flush() {
+    match writer().as_mut() {
+        Some(w) => {
+            let _ = w.flush();
+        }
+        None => {
+            let _ = std::io::stdout().flush();
+            let _ = std::io::stderr().flush();
+        }
+    }
+}
+
+/// Lets the macros pass an error along as `&dyn Debug` when, and only when, its type implements
+/// `Debug`, without adding a `Debug` bound to the macros.
+/// `(&ErrorField(&e)).debug_field()` picks `DebugField` if it applies, else `NoField`.
+#[doc(hidden)]
+pub mod __private {
+    use crate::Message;
+    use std::cell::Cell;
+    use std::fmt::{Debug, Display};
+    use std::ops::ControlFlow;
+    use std::sync::mpsc::{Receiver, RecvError, RecvTimeoutError, TryRecvError};
+
+    pub struct ErrorField<'a, T>(pub &'a T);
+
+    pub trait DebugField {
+        fn debug_field(&self) -> Option<&dyn Debug>;
+    }
+
+    impl<T: Debug> DebugField for ErrorField<'_, T> {
+        fn debug_field(&self) -> Option<&dyn Debug> {
+            Some(self.0)
+        }
+    }
+
+    pub trait NoField {
+        fn debug_field(&self) -> Option<&dyn Debug> {
+            None
+        }
+    }
+
+    impl<T> NoField for &ErrorField<'_, T> {}
+
+    /// Lets the message arms take anything implementing [`Message`](crate::Message), like a
+    /// closure, while still taking any `Display` type: `(&MessageArg::new(m)).message_text()`
+    /// picks `ViaMessage` if it applies, else `ViaDisplay`.
+    pub struct MessageArg<T>(Cell<Option<T>>);
+
+    impl<T> MessageArg<T> {
+        pub fn new(message: T) -> Self {
+            MessageArg(Cell::new(Some(message)))
+        }
+
+        fn take(&self) -> T {
+            self.0.take().expect("message already taken")
+        }
+    }
+
+    pub trait ViaMessage {
+        type Text: Display;
+
+        fn message_text(&self) -> Self::Text;
+    }
+
+    impl<T: Message> ViaMessage for MessageArg<T> {
+        type Text = T::Text;
+
+        fn message_text(&self) -> T::Text {
+            self.take().into_text()
+        }
+    }
+
+    pub trait ViaDisplay {
+        type Text: Display;
+
+        fn message_text(&self) -> Self::Text;
+    }
+
+    impl<T: Display> ViaDisplay for &MessageArg<T> {
+        type Text = T;
+
+        fn message_text(&self) -> T {
+            self.take()
+        }
+    }
+
+    /// What `recv_break!` does with a receiver or the result of receiving: `Continue(Some(v))`
+    /// unwraps to `v`, `Continue(None)` calls `continue`, and `Break(())` calls `break`.
+    /// Implemented for `&Receiver<T>` so `rx.recv_step()` borrows the receiver.
+    pub trait RecvStep<T> {
+        fn recv_step(self) -> ControlFlow<(), Option<T>>;
+    }
+
+    impl<T> RecvStep<T> for &Receiver<T> {
+        fn recv_step(self) -> ControlFlow<(), Option<T>> {
+            self.recv().recv_step()
+        }
+    }
+
+    impl<T> RecvStep<T> for Result<T, RecvError> {
+        fn recv_step(self) -> ControlFlow<(), Option<T>> {
+            match self {
+                Ok(v) => ControlFlow::Continue(Some(v)),
+                Err(RecvError) => ControlFlow::Break(()),
+            }
+        }
+    }
+
+    impl<T> RecvStep<T> for Result<T, TryRecvError> {
+        fn recv_step(self) -> ControlFlow<(), Option<T>> {
+            match self {
+                Ok(v) => ControlFlow::Continue(Some(v)),
+                Err(TryRecvError::Empty) => ControlFlow::Continue(None),
+                Err(TryRecvError::Disconnected) => ControlFlow::Break(()),
+            }
+        }
+    }
+
+    impl<T> RecvStep<T> for Result<T, RecvTimeoutError> {
+        fn recv_step(self) -> ControlFlow<(), Option<T>> {
+            match self {
+                Ok(v) => ControlFlow::Continue(Some(v)),
+                Err(RecvTimeoutError::Timeout) => ControlFlow::Continue(None),
+                Err(RecvTimeoutError::Disconnected) => ControlFlow::Break(()),
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! Everything needed to use the crate, in one import.
+//! ```
+//! use loop_unwrap::prelude::*;
+//!
+//! let mut sum = 0;
+//! for input in &["1", "two", "3"] {
+//!     sum += unwrap_continue!(input.parse::<i32>());
+//! }
+//! assert_eq!(sum, 4);
+//! assert_eq!(Some(5).to_option(), Some(5));
+//! ```
+
+pub use crate::{
+    AsOption, IsEmpty, IsOption, LoopSkip, LoopUnwrap, SkipError, SkipExt, ToOption, ToResultLike,
+};
+
+pub use crate::{
+    break_ok_or_continue, check, for_each_ok, parse_continue, unwrap_break, unwrap_break_dbg,
+    unwrap_break_default, unwrap_break_eprintln, unwrap_break_err, unwrap_break_ok,
+    unwrap_break_or, unwrap_break_with_msg, unwrap_continue, unwrap_continue_at,
+    unwrap_continue_await, unwrap_continue_cf, unwrap_continue_classified, unwrap_continue_dbg,
+    unwrap_continue_err, unwrap_continue_err_eprintln, unwrap_continue_flat, unwrap_continue_if,
+    unwrap_continue_loc, unwrap_continue_nonempty, unwrap_continue_nonzero,
+    unwrap_continue_or_return, unwrap_continue_some, unwrap_continue_strict, unwrap_continue_until,
+    unwrap_default, unwrap_exit, unwrap_next, unwrap_push_continue, unwrap_retry, unwrap_return,
+    unwrap_return_err,
+};
+
+#[cfg(feature = "std")]
+pub use crate::{
+    read_line_continue, recv_break, unwrap_break_timeout, unwrap_continue_catch,
+    unwrap_retry_backoff,
+};
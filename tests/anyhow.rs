@@ -0,0 +1,42 @@
+#![cfg(feature = "anyhow")]
+
+use loop_unwrap::unwrap_continue;
+
+#[test]
+fn report_converts_and_pushes_errors() {
+    let mut reports: Vec<anyhow::Error> = Vec::new();
+    let mut sum = 0;
+    for input in &["1", "two", "3", ""] {
+        sum += unwrap_continue!(input.parse::<i32>(), report: reports);
+    }
+    'lines: for line in &["4.5", "x"] {
+        for _ in 0..2 {
+            let n: f64 = unwrap_continue!(line.parse(), 'lines, report: &mut reports);
+            sum += n as i32;
+        }
+    }
+    assert_eq!(sum, 12);
+    let messages: Vec<String> = reports.iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "invalid digit found in string",
+            "cannot parse integer from empty string",
+            "invalid float literal",
+        ]
+    );
+    assert!(reports[0].is::<std::num::ParseIntError>());
+}
+
+#[test]
+fn report_accepts_anyhow_errors() {
+    let mut reports = Vec::new();
+    for input in &["1", "x"] {
+        let result = input
+            .parse::<i32>()
+            .map_err(|e| anyhow::Error::new(e).context(format!("parsing {:?}", input)));
+        unwrap_continue!(result, report: reports);
+    }
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].to_string(), "parsing \"x\"");
+}
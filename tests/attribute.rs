@@ -0,0 +1,62 @@
+#![cfg(feature = "attribute")]
+
+use loop_unwrap::loop_unwrap;
+use std::num::ParseIntError;
+
+#[loop_unwrap]
+fn sum_valid(inputs: &[&str]) -> i32 {
+    let mut sum = 0;
+    for input in inputs {
+        sum += input.parse::<i32>()?;
+    }
+    sum
+}
+
+#[loop_unwrap]
+fn first_row_sums(rows: &[&str]) -> Result<Vec<i32>, ParseIntError> {
+    let first: i32 = rows[0].split(',').next().unwrap().parse()?; // outside a loop, still returns
+    let mut sums = vec![first];
+    let mut i = 0;
+    while i < rows.len() {
+        let row = rows[i];
+        i += 1;
+        let mut sum = 0;
+        for field in row.split(',') {
+            sum += field.parse::<i32>()?; // skips the field, not the row
+        }
+        let parse = |s: &str| -> Result<i32, ParseIntError> { Ok(s.parse::<i32>()? * 10) };
+        sums.push(sum + parse("1")?);
+    }
+    Ok(sums)
+}
+
+#[loop_unwrap]
+fn nested(grid: &[Vec<Option<i32>>]) -> i32 {
+    let mut total = 0;
+    for row in grid {
+        let first = row.first()?; // an empty row is skipped
+        for cell in row {
+            total += (*cell)?; // skips the cell, not the row
+        }
+        total += first.unwrap_or(0) * 100;
+    }
+    total
+}
+
+#[test]
+fn question_marks_in_loops_continue() {
+    assert_eq!(sum_valid(&["1", "two", "3"]), 4);
+    assert_eq!(
+        first_row_sums(&["1,2", "x,3", "4,"]).unwrap(),
+        vec![1, 13, 13, 14]
+    );
+    assert_eq!(
+        nested(&[vec![Some(1), None, Some(2)], vec![], vec![None]]),
+        103
+    );
+}
+
+#[test]
+fn question_marks_outside_loops_return() {
+    assert!(first_row_sums(&["x,1"]).is_err());
+}
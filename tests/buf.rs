@@ -0,0 +1,80 @@
+use loop_unwrap::unwrap_continue;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+struct StackBuf {
+    bytes: [u8; 32],
+    len: usize,
+}
+
+impl StackBuf {
+    fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.bytes[..self.len]).unwrap()
+    }
+}
+
+impl fmt::Write for StackBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.bytes.len() {
+            return Err(fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[test]
+fn writes_messages_into_a_fixed_size_buffer_without_allocating() {
+    let mut buf = StackBuf {
+        bytes: [0; 32],
+        len: 0,
+    };
+    let values = [Some(1), None, Some(3), None];
+    let before = ALLOCATIONS.with(Cell::get);
+
+    let mut sum = 0;
+    for (i, value) in values.iter().enumerate() {
+        sum += unwrap_continue!(*value, buf: &mut buf, "skipped {};", i);
+    }
+    'outer: for _ in 0..1 {
+        for _ in 0..2 {
+            unwrap_continue!(None::<()>, 'outer, buf: &mut buf, "outer;");
+        }
+    }
+    'last: for _ in 0..1 {
+        for _ in 0..2 {
+            unwrap_continue!(None::<()>, buf: &mut buf, "last;", 'last);
+        }
+    }
+    for _ in 0..1 {
+        unwrap_continue!(false, buf: &mut buf, "this one doesn't fit");
+    }
+
+    assert_eq!(ALLOCATIONS.with(Cell::get), before);
+    assert_eq!(sum, 4);
+    // The last message didn't fit, and the error from the buffer was ignored.
+    assert_eq!(buf.as_str(), "skipped 1;skipped 3;outer;last;");
+}
@@ -0,0 +1,73 @@
+use loop_unwrap::unwrap_continue;
+use std::ptr;
+
+// not Clone, so the macro can't be cloning it
+struct Frame(Vec<u64>);
+
+fn frames() -> Vec<Result<Frame, &'static str>> {
+    vec![
+        Ok(Frame(vec![1; 100_000])),
+        Err("dropped"),
+        Ok(Frame(vec![2; 100_000])),
+    ]
+}
+
+#[test]
+fn borrows_the_value_in_place() {
+    let frames = frames();
+    let mut seen = Vec::new();
+    for frame in &frames {
+        unwrap_continue!(frame, by_ref: pixels);
+        let pixels: &Frame = pixels;
+        seen.push(pixels as *const Frame);
+    }
+    assert_eq!(seen.len(), 2);
+    assert!(ptr::eq(seen[0], frames[0].as_ref().unwrap()));
+    assert!(ptr::eq(seen[1], frames[2].as_ref().unwrap()));
+}
+
+#[test]
+fn leaves_the_value_usable_after_the_loop() {
+    let frames = frames();
+    let buffers: Vec<*const u64> = frames
+        .iter()
+        .map(|f| f.as_ref().map_or(ptr::null(), |f| f.0.as_ptr()))
+        .collect();
+    let mut sum = 0;
+    for i in 0..frames.len() {
+        unwrap_continue!(frames[i], by_ref: frame);
+        assert_eq!(frame.0.as_ptr(), buffers[i]);
+        sum += frame.0[0];
+    }
+    assert_eq!(sum, 3);
+    assert!(frames[0].is_ok());
+}
+
+#[test]
+fn by_ref_with_labels() {
+    let batches = [vec![Some(1), Some(2)], vec![None, Some(3)], vec![Some(4)]];
+    let mut seen = Vec::new();
+    'a: for batch in &batches {
+        for n in batch {
+            unwrap_continue!(n, 'a, by_ref: n);
+            seen.push(*n);
+        }
+    }
+    'b: for batch in &batches {
+        for n in batch {
+            unwrap_continue!(n, by_ref: n, 'b);
+            seen.push(*n);
+        }
+    }
+    assert_eq!(seen, vec![1, 2, 4, 1, 2, 4]);
+}
+
+#[test]
+fn keeps_a_temporary_alive() {
+    let mut lengths = Vec::new();
+    for i in 0..4 {
+        unwrap_continue!(frames().swap_remove(i % 3), by_ref: frame);
+        lengths.push(frame.0.len());
+    }
+    assert_eq!(lengths, vec![100_000, 100_000, 100_000]);
+}
@@ -0,0 +1,45 @@
+use loop_unwrap::{unwrap_continue, unwrap_continue_catch};
+
+fn checked_half(n: i32) -> i32 {
+    if n % 2 != 0 {
+        panic!("{} is odd", n);
+    }
+    n / 2
+}
+
+#[test]
+fn caught_panics_skip_the_iteration() {
+    let mut halves = Vec::new();
+    for n in 1..=6 {
+        halves.push(unwrap_continue_catch!(checked_half(n)));
+    }
+    assert_eq!(halves, vec![1, 2, 3]);
+}
+
+#[test]
+fn a_panicking_closure_can_borrow_mutably() {
+    let mut calls = 0;
+    let mut process = |n: i32| {
+        calls += 1;
+        checked_half(n)
+    };
+    let mut sum = 0;
+    for n in &[2, 3, 4] {
+        sum += unwrap_continue_catch!(process(*n), "recovered from panic");
+    }
+    assert_eq!((sum, calls), (3, 3));
+}
+
+#[test]
+fn labels_and_nested_unwrapping() {
+    let rows = [vec!["2", "4"], vec!["3", "8"], vec!["x", "6"]];
+    let mut done = Vec::new();
+    'rows: for (i, row) in rows.iter().enumerate() {
+        for cell in row {
+            let n: i32 = unwrap_continue!(cell.parse());
+            unwrap_continue_catch!(checked_half(n), 'rows, "row {} has an odd number", i);
+        }
+        done.push(i);
+    }
+    assert_eq!(done, vec![0, 2]);
+}
@@ -0,0 +1,94 @@
+use loop_unwrap::{unwrap_continue_classified, SkipError};
+use std::num::ParseIntError;
+
+#[derive(Clone, Debug, PartialEq)]
+enum FetchError {
+    Timeout,
+    NotFound,
+    Forbidden,
+}
+
+impl SkipError for FetchError {
+    fn is_skip(&self) -> bool {
+        matches!(self, FetchError::Timeout | FetchError::NotFound)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Transient;
+
+impl SkipError for Transient {}
+
+#[derive(Debug, PartialEq)]
+struct Fatal(FetchError);
+
+impl From<FetchError> for Fatal {
+    fn from(e: FetchError) -> Self {
+        Fatal(e)
+    }
+}
+
+fn fetch_all(results: Vec<Result<u32, FetchError>>) -> Result<Vec<u32>, Fatal> {
+    let mut fetched = Vec::new();
+    for result in results {
+        fetched.push(unwrap_continue_classified!(result));
+    }
+    Ok(fetched)
+}
+
+#[test]
+fn skips_only_what_is_skip_allows() {
+    let results = vec![
+        Ok(1),
+        Err(FetchError::Timeout),
+        Err(FetchError::NotFound),
+        Ok(2),
+    ];
+    assert_eq!(fetch_all(results), Ok(vec![1, 2]));
+    let results = vec![Ok(1), Err(FetchError::Forbidden), Ok(2)];
+    assert_eq!(fetch_all(results), Err(Fatal(FetchError::Forbidden)));
+}
+
+#[test]
+fn default_is_skip_skips_everything() {
+    fn count(results: Vec<Result<u32, Transient>>) -> Result<usize, Transient> {
+        let mut count = 0;
+        for result in results {
+            unwrap_continue_classified!(result);
+            count += 1;
+        }
+        Ok(count)
+    }
+    assert_eq!(count(vec![Ok(1), Err(Transient), Ok(2)]), Ok(2));
+}
+
+#[test]
+fn errors_without_an_impl_are_skipped() {
+    fn sum(inputs: &[&str]) -> Result<i32, ParseIntError> {
+        let mut sum = 0;
+        for input in inputs {
+            sum += unwrap_continue_classified!(input.parse::<i32>());
+        }
+        Ok(sum)
+    }
+    assert_eq!(sum(&["1", "two", "3"]), Ok(4));
+}
+
+#[test]
+fn label_continues_the_outer_loop() {
+    fn rows(table: &[&[Result<u32, FetchError>]]) -> Result<usize, FetchError> {
+        let mut rows = 0;
+        'rows: for row in table {
+            for cell in row.iter() {
+                let _v = unwrap_continue_classified!(cell.clone(), 'rows);
+            }
+            rows += 1;
+        }
+        Ok(rows)
+    }
+    let table: &[&[Result<u32, FetchError>]] =
+        &[&[Ok(1), Ok(2)], &[Err(FetchError::Timeout), Ok(3)]];
+    assert_eq!(rows(table), Ok(1));
+    let table: &[&[Result<u32, FetchError>]] = &[&[Ok(1), Err(FetchError::Forbidden)]];
+    assert_eq!(rows(table), Err(FetchError::Forbidden));
+}
@@ -0,0 +1,93 @@
+#![cfg(all(feature = "std", not(feature = "quiet")))]
+
+use loop_unwrap::{
+    configure, set_unwrap_writer, take_unwrap_writer, unwrap_break, unwrap_continue,
+    unwrap_continue_err_eprintln, Destination, UnwrapConfig,
+};
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Captured {
+    fn take(&self) -> String {
+        String::from_utf8(std::mem::take(&mut *self.0.lock().unwrap())).unwrap()
+    }
+}
+
+fn skip_two() {
+    for input in &["1", "two"] {
+        let _n: i32 = unwrap_continue!(input.parse(), "Couldn't parse {:?}", input);
+    }
+}
+
+// one test, as the config is shared by every thread
+#[test]
+fn config_formats_every_message() {
+    let captured = Captured::default();
+    set_unwrap_writer(Box::new(captured.clone()));
+
+    skip_two();
+    assert_eq!(captured.take(), "Couldn't parse \"two\"\n");
+
+    configure(UnwrapConfig::new().prefix("[app] "));
+    skip_two();
+    loop {
+        let _n: i32 = unwrap_break!("x".parse(), "giving up");
+    }
+    assert_eq!(
+        captured.take(),
+        "[app] Couldn't parse \"two\"\n[app] giving up\n"
+    );
+
+    configure(UnwrapConfig::new().prefix("[app] ").location(true));
+    let line = line!() + 2;
+    for input in &["x"] {
+        let _n: i32 = unwrap_continue_err_eprintln!(input.parse(), "bad {}", input);
+    }
+    assert_eq!(
+        captured.take(),
+        format!("[app] [{}:{}] bad x\n", file!(), line)
+    );
+
+    // the writer takes precedence over the destination
+    configure(
+        UnwrapConfig::new()
+            .destination(Destination::Stderr)
+            .location(true),
+    );
+    let line = line!() + 2;
+    loop {
+        let _n: i32 = unwrap_break!(None, "no prefix");
+    }
+    assert_eq!(
+        captured.take(),
+        format!("[{}:{}] no prefix\n", file!(), line)
+    );
+
+    configure(UnwrapConfig::new());
+    skip_two();
+    assert_eq!(captured.take(), "Couldn't parse \"two\"\n");
+    take_unwrap_writer();
+}
+
+#[test]
+fn builder_sets_each_field() {
+    let config = UnwrapConfig::new()
+        .prefix("p")
+        .destination(Destination::Stdout)
+        .location(false);
+    assert_ne!(config, UnwrapConfig::new());
+    assert_eq!(config.clone(), config);
+    assert_eq!(UnwrapConfig::new().location(false), UnwrapConfig::default());
+}
@@ -0,0 +1,79 @@
+use loop_unwrap::{unwrap_break, unwrap_continue};
+
+const fn sum_until_none(values: &[Option<u32>]) -> u32 {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < values.len() {
+        let value = values[i];
+        i += 1;
+        sum += unwrap_break!(value, const);
+    }
+    sum
+}
+
+const fn sum_some(values: &[Option<u32>]) -> u32 {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < values.len() {
+        let value = values[i];
+        i += 1;
+        sum += unwrap_continue!(value, const);
+    }
+    sum
+}
+
+const fn last_product(limit: u8) -> u8 {
+    let mut last = 0;
+    let mut row: u8 = 1;
+    'rows: while row <= limit {
+        let mut col: u8 = 1;
+        while col <= limit {
+            last = unwrap_break!(row.checked_mul(col), 'rows, const);
+            col += 1;
+        }
+        row += 1;
+    }
+    last
+}
+
+const fn count_leading_digits(lines: &[&[u8]]) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    'lines: while i < lines.len() {
+        let line = lines[i];
+        i += 1;
+        let mut j = 0;
+        while j < line.len() {
+            let byte = line[j];
+            j += 1;
+            let digit = if byte.is_ascii_digit() {
+                Some(byte)
+            } else {
+                None
+            };
+            unwrap_continue!(digit, const, 'lines);
+            count += 1;
+        }
+    }
+    count
+}
+
+const SUM: u32 = sum_until_none(&[Some(1), Some(2), None, Some(4)]);
+const SUM_SOME: u32 = sum_some(&[Some(1), None, Some(3), None]);
+const PRODUCT: u8 = last_product(20);
+const DIGITS: usize = count_leading_digits(&[b"12a3", b"x1", b"456"]);
+
+#[test]
+fn const_forms_evaluate_at_compile_time() {
+    assert_eq!(SUM, 3);
+    assert_eq!(SUM_SOME, 4);
+    assert_eq!(PRODUCT, 247); // 13 * 20 overflows a u8
+    assert_eq!(DIGITS, 5);
+}
+
+#[test]
+fn const_forms_work_at_runtime() {
+    let values = vec![Some(5), None, Some(6)];
+    assert_eq!(sum_until_none(&values), 5);
+    assert_eq!(sum_some(&values), 11);
+}
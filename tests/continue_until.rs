@@ -0,0 +1,39 @@
+use loop_unwrap::unwrap_continue_until;
+
+#[test]
+fn successes_in_between_keep_the_loop_going() {
+    let inputs = ["x", "x", "1", "x", "x", "2", "x", "x", "3"];
+    let mut fails = 0;
+    let mut seen = Vec::new();
+    for input in &inputs {
+        seen.push(unwrap_continue_until!(input.parse::<i32>(), fails: fails, max: 3));
+    }
+    // six failures, twice `max`, but never three in a row
+    assert_eq!(seen, vec![1, 2, 3]);
+    assert_eq!(fails, 0);
+}
+
+#[test]
+fn breaks_after_max_failures_in_a_row() {
+    let mut fails = 0u8;
+    let mut attempts = 0;
+    let mut seen = Vec::new();
+    for input in &["1", "x", "x", "x", "2"] {
+        attempts += 1;
+        seen.push(unwrap_continue_until!(input.parse::<i32>(), fails: fails, max: 3));
+    }
+    assert_eq!((seen, attempts, fails), (vec![1], 4, 3));
+}
+
+#[test]
+fn label_breaks_the_outer_loop() {
+    let mut fails = 0;
+    let mut batches = 0;
+    'batches: for batch in &[["x", "1"], ["x", "x"], ["2", "3"]] {
+        batches += 1;
+        for input in batch {
+            let _n: i32 = unwrap_continue_until!(input.parse(), fails: fails, max: 2, 'batches);
+        }
+    }
+    assert_eq!(batches, 2);
+}
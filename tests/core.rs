@@ -0,0 +1,41 @@
+#![cfg(all(feature = "std", not(feature = "quiet")))]
+
+use loop_unwrap::{__loop_unwrap_core, set_unwrap_writer};
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// A user-defined variant: `break`s with the number of values taken so far, printing the message.
+macro_rules! break_taken {
+    ($x:expr, $taken:expr, $($msg:tt)+) => {
+        __loop_unwrap_core!($x, { break $taken; }, $($msg)+)
+    };
+}
+
+#[test]
+fn user_macro_built_on_core() {
+    let captured = Captured::default();
+    set_unwrap_writer(Box::new(captured.clone()));
+
+    let mut inputs = vec!["1", "2", "x", "4"].into_iter();
+    let mut taken = 0;
+    let count = loop {
+        let input = break_taken!(inputs.next(), taken, "Out of inputs");
+        let _n: i32 = break_taken!(input.parse(), taken, "Couldn't parse {:?}", input);
+        taken += 1;
+    };
+    assert_eq!(count, 2);
+    assert_eq!(&*captured.0.lock().unwrap(), b"Couldn't parse \"x\"\n");
+}
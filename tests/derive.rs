@@ -0,0 +1,43 @@
+#![cfg(feature = "derive")]
+
+use loop_unwrap::{unwrap_continue, ToOption};
+
+#[derive(ToOption)]
+#[allow(dead_code)]
+enum Outcome<T> {
+    #[to_option(some)]
+    Done(T),
+    Skipped,
+    Failed(String),
+}
+
+#[derive(ToOption)]
+enum Reading {
+    Value { celsius: f32 },
+    Missing,
+}
+
+#[test]
+fn derived_enum_flows_through_unwrap_continue() {
+    let outcomes = vec![
+        Outcome::Done(1),
+        Outcome::Skipped,
+        Outcome::Failed(String::from("nope")),
+        Outcome::Done(3),
+    ];
+    let mut sum = 0;
+    for outcome in outcomes {
+        sum += unwrap_continue!(outcome, "skipped");
+    }
+    assert_eq!(sum, 4);
+}
+
+#[test]
+fn single_field_variant_is_found_without_attribute() {
+    let readings = [Reading::Missing, Reading::Value { celsius: 21.5 }];
+    let mut seen = Vec::new();
+    for reading in readings {
+        seen.push(unwrap_continue!(reading));
+    }
+    assert_eq!(seen, [21.5]);
+}
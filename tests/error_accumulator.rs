@@ -0,0 +1,38 @@
+#![cfg(feature = "std")]
+
+use loop_unwrap::{unwrap_break, unwrap_continue, ErrorAccumulator};
+use std::num::ParseIntError;
+
+#[test]
+fn breaks_with_every_recorded_error() {
+    let mut errors = ErrorAccumulator::new();
+    let mut rows = vec!["1,2", "x,3", "4,", "5,6"].into_iter();
+    let mut sums = Vec::new();
+    let outcome: Result<(), Vec<ParseIntError>> = 'rows: loop {
+        let row = unwrap_break!(rows.next(), with: errors.into_break());
+        let mut sum = 0;
+        for field in row.split(',') {
+            sum += unwrap_continue!(errors.record(field.parse::<i32>()), 'rows);
+        }
+        sums.push(sum);
+    };
+    let errors = outcome.unwrap_err();
+    assert_eq!(sums, vec![3, 11]);
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].to_string(), "invalid digit found in string");
+    assert_eq!(
+        errors[1].to_string(),
+        "cannot parse integer from empty string"
+    );
+}
+
+#[test]
+fn records_nones_and_keeps_values() {
+    let mut errors = ErrorAccumulator::default();
+    assert!(errors.is_empty());
+    assert_eq!(errors.record(Some(1)), Some(1));
+    assert_eq!(errors.record(None::<i32>), None);
+    assert_eq!(errors.record(None::<i32>), None);
+    assert_eq!(errors.errors(), &[(), ()]);
+    assert_eq!(errors.into_break::<i32>(), Err(vec![(), ()]));
+}
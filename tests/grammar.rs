@@ -0,0 +1,165 @@
+//! Every call shape of the macros sharing the `(x, label, message)` grammar, generated from a
+//! table: each macro is expanded with no label, with the label of the inner or the outer loop,
+//! and with every message before and after the label, and each expansion has to compile and
+//! continue or break the loop it names. Add a line to a table when adding an arm or a macro.
+
+#![cfg(feature = "std")]
+// Each expansion declares both labels, but only uses one.
+#![allow(unused_labels)]
+
+use loop_unwrap::*;
+
+const INPUTS: [Result<i32, &str>; 3] = [Ok(1), Err("none"), Ok(3)];
+
+/// Runs `$call` with `$x` set to each of `INPUTS`, in an `'inner` loop run twice by an `'outer`
+/// one. Returns the values `$call` evaluated to, plus 10 on the second round, with -1 at the end
+/// of each round that finished.
+macro_rules! run {
+    ($outer:lifetime, $inner:lifetime, |$x:ident| $call:expr) => {{
+        let mut trace = Vec::new();
+        $outer: for round in 0..2 {
+            $inner: for $x in INPUTS.iter().copied() {
+                let v: i32 = $call;
+                trace.push(v + round * 10);
+            }
+            trace.push(-1);
+        }
+        trace
+    }};
+}
+
+/// Same as `run!`, but with `loop`s, for the macros breaking with `Err(e)`. Returns the trace,
+/// with -2 at the end of a round whose inner loop broke with an error, and whether the outer
+/// loop did.
+macro_rules! run_err {
+    ($outer:lifetime, $inner:lifetime, |$x:ident| $call:expr) => {{
+        let mut trace = Vec::new();
+        let outer: Result<(), &str> = $outer: loop {
+            let mut inputs = INPUTS.iter().copied();
+            let inner: Result<(), &str> = $inner: loop {
+                let $x = match inputs.next() {
+                    Some(input) => input,
+                    None => break Ok(()),
+                };
+                let v: i32 = $call;
+                trace.push(v);
+            };
+            trace.push(if inner.is_err() { -2 } else { -1 });
+            break Ok(());
+        };
+        (trace, outer.is_err())
+    }};
+}
+
+/// Runs `$call` with `$run` and compares the result, naming the call if it's wrong. The labels
+/// and `$x` are passed in so they're the same as the ones `$call` uses: labels and variables
+/// introduced by a macro are hygienic.
+macro_rules! check {
+    ($run:ident, $outer:lifetime, $inner:lifetime, |$x:ident| $call:expr, $expected:expr) => {
+        assert_eq!(
+            $run!($outer, $inner, |$x| $call),
+            $expected,
+            "{}",
+            stringify!($call)
+        );
+    };
+}
+
+/// Expands `$mac` in every shape, with `$run` as the harness, checking the result against
+/// `inner` for the shapes leaving the inner loop and `outer` for the ones naming the outer one.
+/// `both` are messages that can come before or after the label, `first` the ones (like format
+/// strings) that have to come after it.
+macro_rules! shapes {
+    (
+        $($test:ident: $mac:ident, $run:ident, inner: $inner:expr, outer: $outer:expr,
+        both: [$(($($both:tt)*)),* $(,)?],
+        first: [$(($($first:tt)*)),* $(,)?];)*
+    ) => {$(
+        #[test]
+        fn $test() {
+            let (inner, outer) = ($inner, $outer);
+            check!($run, 'outer, 'inner, |x| $mac!(x), inner);
+            check!($run, 'outer, 'inner, |x| $mac!(x, 'inner), inner);
+            check!($run, 'outer, 'inner, |x| $mac!(x, 'outer), outer);
+            $(
+                check!($run, 'outer, 'inner, |x| $mac!(x, $($both)*), inner);
+                check!($run, 'outer, 'inner, |x| $mac!(x, 'inner, $($both)*), inner);
+                check!($run, 'outer, 'inner, |x| $mac!(x, $($both)*, 'inner), inner);
+                check!($run, 'outer, 'inner, |x| $mac!(x, 'outer, $($both)*), outer);
+                check!($run, 'outer, 'inner, |x| $mac!(x, $($both)*, 'outer), outer);
+            )*
+            $(
+                check!($run, 'outer, 'inner, |x| $mac!(x, $($first)*), inner);
+                check!($run, 'outer, 'inner, |x| $mac!(x, 'inner, $($first)*), inner);
+                check!($run, 'outer, 'inner, |x| $mac!(x, 'outer, $($first)*), outer);
+            )*
+        }
+    )*};
+}
+
+fn continued() -> (Vec<i32>, Vec<i32>) {
+    (vec![1, 3, -1, 11, 13, -1], vec![1, 11])
+}
+
+fn broken() -> (Vec<i32>, Vec<i32>) {
+    (vec![1, -1, 11, -1], vec![1])
+}
+
+fn broken_with_err() -> ((Vec<i32>, bool), (Vec<i32>, bool)) {
+    ((vec![1, -2], false), (vec![1], true))
+}
+
+shapes! {
+    unwrap_continue_shapes: unwrap_continue, run, inner: continued().0, outer: continued().1,
+        both: [
+            ("message"),
+            (String::from("message")),
+            (|| "lazy"),
+            (|e| e),
+            (tag: "T", "message"),
+            (via: &mut |_: &str| {}, "message"),
+            (buf: &mut String::new(), "message"),
+            (on_err: {}),
+            (else |_e| {}),
+            (collect: Vec::new()),
+            (msg: default),
+        ],
+        first: [
+            ("{} {}", "format", 1),
+            (tag: "T", "{}", "format"),
+            (via: &mut |_: &str| {}, "{}", "format"),
+            (buf: &mut String::new(), "{}", "format"),
+        ];
+    unwrap_continue_err_shapes: unwrap_continue_err, run, inner: continued().0, outer: continued().1,
+        both: [("message"), (|e| e), (else |_e| {})],
+        first: [("{} {}", "format", 1)];
+    unwrap_continue_err_eprintln_shapes: unwrap_continue_err_eprintln, run,
+        inner: continued().0, outer: continued().1,
+        both: [("message")],
+        first: [("{} {}", "format", 1)];
+    unwrap_continue_loc_shapes: unwrap_continue_loc, run, inner: continued().0, outer: continued().1,
+        both: [("message")],
+        first: [("{} {}", "format", 1)];
+    unwrap_break_shapes: unwrap_break, run, inner: broken().0, outer: broken().1,
+        both: [
+            ("message"),
+            (String::from("message")),
+            (|| "lazy"),
+            (on_err: {}),
+        ],
+        first: [("{} {}", "format", 1)];
+    unwrap_break_eprintln_shapes: unwrap_break_eprintln, run, inner: broken().0, outer: broken().1,
+        both: [("message")],
+        first: [("{} {}", "format", 1)];
+    unwrap_break_err_shapes: unwrap_break_err, run_err,
+        inner: broken_with_err().0, outer: broken_with_err().1,
+        both: [
+            ("message"),
+            (err: "replaced"),
+            (err: "replaced", "message"),
+            (map: |e| e),
+            (map: |e| e, "message"),
+            (|| "lazy message"),
+        ],
+        first: [("{} {}", "format", 1)];
+}
@@ -0,0 +1,45 @@
+#![cfg(all(feature = "std", not(feature = "quiet")))]
+
+use loop_unwrap::{set_unwrap_writer, take_unwrap_writer, unwrap_continue_loc};
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn message_starts_with_call_site() {
+    let captured = Captured::default();
+    set_unwrap_writer(Box::new(captured.clone()));
+
+    let mut lines = Vec::new();
+    for input in &["1", "two"] {
+        lines.push(line!() + 1);
+        let _n: i32 = unwrap_continue_loc!(input.parse(), "Couldn't parse {:?}", input);
+    }
+    'outer: for _ in 0..1 {
+        lines.push(line!() + 1);
+        unwrap_continue_loc!(None::<()>, 'outer);
+    }
+    take_unwrap_writer();
+
+    let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+    assert_eq!(
+        output,
+        format!(
+            "[{file}:{}] Couldn't parse \"two\"\n[{file}:{}]\n",
+            lines[1],
+            lines[2],
+            file = file!()
+        )
+    );
+}
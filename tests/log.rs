@@ -0,0 +1,59 @@
+#![cfg(all(feature = "log", not(feature = "tracing")))]
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use loop_unwrap::{unwrap_break, unwrap_break_err, unwrap_continue};
+use std::sync::Mutex;
+
+struct CapturingLogger {
+    records: Mutex<Vec<(Level, String, String)>>,
+}
+
+impl Log for CapturingLogger {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.records.lock().unwrap().push((
+            record.level(),
+            record.target().to_string(),
+            record.args().to_string(),
+        ));
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CapturingLogger = CapturingLogger {
+    records: Mutex::new(Vec::new()),
+};
+
+#[test]
+fn messages_go_through_log() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(LevelFilter::Trace);
+
+    for input in &["1", "two"] {
+        let _n: i32 = unwrap_continue!(input.parse(), "continue: {}", input);
+    }
+    loop {
+        let _n: i32 = unwrap_break!("three".parse(), "break");
+    }
+    let mut inputs = ["4", "four"].iter();
+    let result: Result<i32, _> = loop {
+        let n = unwrap_break_err!(inputs.next().unwrap().parse::<i32>(), "break_err");
+        if n > 10 {
+            break Ok(n);
+        }
+    };
+    assert!(result.is_err());
+
+    let records = LOGGER.records.lock().unwrap();
+    let expected = ["continue: two", "break", "break_err"];
+    assert_eq!(records.len(), expected.len());
+    for ((level, target, message), expected) in records.iter().zip(&expected) {
+        assert_eq!(*level, Level::Warn);
+        assert_eq!(target, "loop_unwrap");
+        assert_eq!(message, expected);
+    }
+}
@@ -0,0 +1,340 @@
+#![cfg(all(feature = "capture", not(feature = "quiet")))]
+
+use loop_unwrap::prelude::*;
+use loop_unwrap::{
+    capture_messages, set_default_message, take_default_message, take_messages,
+    DEFAULT_SKIP_MESSAGE,
+};
+use std::time::Instant;
+
+const INPUTS: [&str; 2] = ["1", "x"];
+
+#[test]
+fn continue_macros() {
+    capture_messages();
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), "continue {}", input);
+    }
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), tag: "T", "tagged");
+    }
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), |e| e);
+    }
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue_err!(input.parse(), "continue_err {}", input);
+    }
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue_err_eprintln!(input.parse(), "continue_err_eprintln");
+    }
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue_strict!(input.parse::<i32>().ok(), "continue_strict");
+    }
+    for input in &INPUTS {
+        let _n = parse_continue!(input, i32, "parse_continue {}", input);
+    }
+    for value in &[Some(1), None] {
+        let _n = unwrap_continue_some!(*value, "continue_some");
+    }
+    for value in &[Some(1), None] {
+        let _n = unwrap_continue_dbg!(*value, "continue_dbg");
+    }
+    for value in &[1, 0] {
+        let _n = unwrap_continue_nonzero!(*value, "continue_nonzero");
+    }
+    for value in &[Some(Some(1)), Some(None)] {
+        let _n = unwrap_continue_flat!(*value, "continue_flat");
+    }
+    for value in &[Some(2), Some(1)] {
+        let _n = unwrap_continue_if!(*value, |n| *n > 1, "continue_if");
+    }
+    let mut pushed = Vec::new();
+    for input in &INPUTS {
+        unwrap_push_continue!(input.parse::<i32>(), pushed, "push_continue");
+    }
+    let mut iter = vec![1].into_iter();
+    for _ in 0..2 {
+        let _n = unwrap_next!(iter, "next");
+    }
+    assert_eq!(
+        take_messages(),
+        vec![
+            "continue x",
+            "[T] tagged",
+            "invalid digit found in string",
+            "continue_err x",
+            "continue_err_eprintln",
+            "continue_strict",
+            "parse_continue x",
+            "continue_some",
+            "\"continue_dbg\"",
+            "continue_nonzero",
+            "continue_flat",
+            "continue_if",
+            "push_continue",
+            "next",
+        ]
+    );
+}
+
+#[test]
+fn break_macros() {
+    capture_messages();
+    for input in &INPUTS {
+        let _n: i32 = unwrap_break!(input.parse(), "break {}", input);
+    }
+    let _: i32 = loop {
+        let _n: i32 = unwrap_break!("x".parse(), with: 0, "break with");
+    };
+    let _: Result<(), _> = loop {
+        let _n: i32 = unwrap_break_err!("x".parse(), "break_err");
+    };
+    let _: i32 = loop {
+        let _n: i32 = unwrap_break_or!("x".parse(), 0, "break_or");
+    };
+    let _: i32 = loop {
+        let _n: i32 = unwrap_break_default!("x".parse(), "break_default");
+    };
+    for input in &INPUTS {
+        let _n: i32 = unwrap_break_eprintln!(input.parse(), "break_eprintln");
+    }
+    for value in &[Some(1), None] {
+        let _n = unwrap_break_dbg!(*value, "break_dbg");
+    }
+    let mut failures = 0;
+    loop {
+        let _n: i32 = unwrap_retry!("x".parse(), failures, 2, "retry");
+    }
+    let deadline = Instant::now();
+    loop {
+        let _n: i32 = unwrap_break_timeout!(Some(1), deadline: deadline, "timeout");
+    }
+    assert_eq!(
+        take_messages(),
+        vec![
+            "break x",
+            "break with",
+            "break_err",
+            "break_or",
+            "break_default",
+            "break_eprintln",
+            "\"break_dbg\"",
+            "retry",
+            "retry",
+            "timeout",
+        ]
+    );
+}
+
+fn parse_or_zero(input: &str) -> i32 {
+    unwrap_return!(input.parse(), 0, "return {}", input)
+}
+
+fn parse_or_err(input: &str) -> Result<i32, std::num::ParseIntError> {
+    Ok(unwrap_return_err!(input.parse(), "return_err {}", input))
+}
+
+#[test]
+fn other_macros() {
+    capture_messages();
+    assert_eq!(parse_or_zero("x"), 0);
+    assert!(parse_or_err("y").is_err());
+    assert_eq!(unwrap_default!("x".parse::<i32>(), 3, "default"), 3);
+    let _ = check!("x".parse::<i32>(), "check {}", 1);
+    assert_eq!(
+        take_messages(),
+        vec!["return x", "return_err y", "default", "check 1"]
+    );
+}
+
+#[test]
+fn capturing_stops_at_take() {
+    capture_messages();
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), "captured");
+    }
+    assert_eq!(take_messages(), vec!["captured"]);
+    assert!(take_messages().is_empty());
+}
+
+struct Code(u8);
+
+impl loop_unwrap::Message for Code {
+    type Text = String;
+
+    fn into_text(self) -> String {
+        format!("code {}", self.0)
+    }
+}
+
+#[test]
+fn message_kinds() {
+    capture_messages();
+    let mut built = 0;
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), "str");
+    }
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), String::from("string"));
+    }
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), || {
+            built += 1;
+            format!("closure {}", input)
+        });
+    }
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), 42);
+    }
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), Code(7));
+    }
+    loop {
+        let _n: i32 = unwrap_break!("x".parse(), || "break closure");
+    }
+    assert_eq!(built, 1);
+    assert_eq!(
+        take_messages(),
+        vec![
+            "str",
+            "string",
+            "closure x",
+            "42",
+            "code 7",
+            "break closure"
+        ]
+    );
+}
+
+#[test]
+fn closure_messages_beyond_continue_and_break() {
+    capture_messages();
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue_err!(input.parse(), || "continue_err");
+    }
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue_flat!(input.parse().map(Some), || "continue_flat");
+    }
+    for n in &[1, 0] {
+        let _n = unwrap_continue_nonzero!(*n, || format!("continue_nonzero {}", n));
+    }
+    let mut words = INPUTS.iter();
+    loop {
+        let _word = unwrap_next!(words, || "next");
+    }
+    let _: Result<(), _> = loop {
+        let _n: i32 = unwrap_break_err!("x".parse(), || "break_err");
+    };
+    let n: i32 = unwrap_default!("x".parse(), 7, || "default");
+    assert_eq!(n, 7);
+    assert_eq!(
+        take_messages(),
+        vec![
+            "continue_err",
+            "continue_flat",
+            "continue_nonzero 0",
+            "next",
+            "break_err",
+            "default"
+        ]
+    );
+}
+
+#[test]
+fn non_literal_messages() {
+    capture_messages();
+    let owned = String::from("owned");
+    let borrowed = String::from("borrowed");
+    let code = 404;
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), owned.clone());
+    }
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), &borrowed);
+    }
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), format!("formatted {}", input));
+    }
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), code);
+    }
+    'a: for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), &borrowed, 'a);
+    }
+    'b: for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), 'b, code);
+    }
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue_err!(input.parse(), owned.clone());
+    }
+    loop {
+        let _n: i32 = unwrap_break!("x".parse(), &borrowed);
+    }
+    loop {
+        let _n: i32 = unwrap_break!("x".parse(), code);
+    }
+    assert_eq!(
+        take_messages(),
+        vec![
+            "owned",
+            "borrowed",
+            "formatted x",
+            "404",
+            "borrowed",
+            "404",
+            "owned",
+            "borrowed",
+            "404",
+        ]
+    );
+}
+
+// the only test using `msg: default`, as the default message is shared by every thread
+#[test]
+fn default_message() {
+    capture_messages();
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), msg: default);
+    }
+    set_default_message("custom default");
+    'a: for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), 'a, msg: default);
+    }
+    assert_eq!(take_default_message().as_deref(), Some("custom default"));
+    'b: for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), msg: default, 'b);
+    }
+    assert_eq!(
+        take_messages(),
+        vec![DEFAULT_SKIP_MESSAGE, "custom default", DEFAULT_SKIP_MESSAGE]
+    );
+}
+
+#[test]
+fn variables_named_like_keywords() {
+    capture_messages();
+    let by_ref = "by_ref message";
+    let default_msg = "my own message";
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), by_ref);
+    }
+    for input in &INPUTS {
+        let _n: i32 = unwrap_continue!(input.parse(), default_msg);
+    }
+    assert_eq!(take_messages(), vec!["by_ref message", "my own message"]);
+}
+
+#[test]
+fn index_messages() {
+    capture_messages();
+    for (i, input) in ["1", "x", "3", "y"].iter().enumerate() {
+        let _n: i32 = unwrap_continue_at!(input.parse(), i, "failed at {}: {}", input);
+    }
+    'a: for (i, input) in INPUTS.iter().enumerate() {
+        let _n: i32 = unwrap_continue_at!(input.parse(), 'a, i);
+    }
+    assert_eq!(
+        take_messages(),
+        vec!["failed at 1: x", "failed at 3: y", "failed at 1"]
+    );
+}
@@ -0,0 +1,181 @@
+#![cfg(feature = "metrics")]
+
+use loop_unwrap::prelude::*;
+use loop_unwrap::{stats, UnwrapStats};
+use std::future::Future;
+use std::sync::{mpsc, Mutex};
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use std::thread;
+
+// the counters are shared by every test in this file
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn since(before: UnwrapStats) -> UnwrapStats {
+    let now = stats();
+    UnwrapStats {
+        continues: now.continues - before.continues,
+        breaks: now.breaks - before.breaks,
+        err_breaks: now.err_breaks - before.err_breaks,
+    }
+}
+
+#[test]
+fn counts_skips_and_breaks() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let before = stats();
+    for input in &["1", "x", "y", "4"] {
+        let _n: i32 = unwrap_continue!(input.parse());
+    }
+    for input in &["1", "x"] {
+        let _n: i32 = unwrap_continue_err!(input.parse());
+    }
+    for input in &["1", "x", "3"] {
+        let _n: i32 = unwrap_break!(input.parse());
+    }
+    let result: Result<(), _> = loop {
+        let _n: i32 = unwrap_break_err!("x".parse());
+    };
+    assert!(result.is_err());
+    assert_eq!(
+        since(before),
+        UnwrapStats {
+            continues: 3,
+            breaks: 1,
+            err_breaks: 1,
+        }
+    );
+}
+
+#[test]
+fn successes_are_not_counted() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let before = stats();
+    let mut sum = 0;
+    for input in &["1", "2"] {
+        sum += unwrap_continue!(input.parse::<i32>());
+        sum += unwrap_break!(input.parse::<i32>());
+    }
+    assert_eq!(sum, 6);
+    assert_eq!(since(before), UnwrapStats::default());
+}
+
+#[test]
+fn counts_across_threads() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let before = stats();
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            thread::spawn(|| {
+                for i in 0..100 {
+                    let _n = unwrap_continue!(if i % 2 == 0 { Some(i) } else { None });
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(since(before).continues, 200);
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    fn raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            raw_waker()
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    let waker = unsafe { Waker::from_raw(raw_waker()) };
+    let mut context = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+#[test]
+fn counts_every_macro() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let before = stats();
+    for n in &[1, 2] {
+        let _n = unwrap_continue_if!(Some(*n), |n| *n > 1);
+    }
+    let mut fails = 0;
+    loop {
+        let _n: i32 = unwrap_continue_until!(None, fails: fails, max: 2);
+    }
+    for input in &["1", "x"] {
+        let _n: i32 = unwrap_continue_loc!(input.parse());
+    }
+    for (i, input) in ["1", "x"].iter().enumerate() {
+        let _n: i32 = unwrap_continue_at!(input.parse(), i);
+    }
+    for n in &[1, 0] {
+        let _n = unwrap_continue_catch!(10 / *n);
+    }
+    block_on(async {
+        for n in &[Some(1), None] {
+            let _n = unwrap_continue_await!(async { *n });
+        }
+    });
+    for n in &[Some(1), None] {
+        let _n = unwrap_continue_dbg!(*n, "dbg");
+    }
+    for n in &[Some(1), None] {
+        let _n = unwrap_break_dbg!(*n, "dbg");
+    }
+    for input in &["1", "x"] {
+        let _n: i32 = unwrap_continue_err_eprintln!(input.parse());
+    }
+    for input in &["1", "x"] {
+        let _n: i32 = unwrap_break_eprintln!(input.parse());
+    }
+    for n in &[Some(Some(1)), Some(None)] {
+        let _n = unwrap_continue_flat!(*n);
+    }
+    let _ = [Some(1), None].iter().try_for_each(|n| {
+        let _n = unwrap_continue_cf!(*n);
+        std::ops::ControlFlow::<()>::Continue(())
+    });
+    let mut failures = 0;
+    loop {
+        let _n: i32 = unwrap_retry!(None, failures, 2);
+    }
+    let mut numbers = vec![1].into_iter();
+    loop {
+        let _n = unwrap_next!(numbers);
+    }
+    let (tx, rx) = mpsc::channel();
+    tx.send(1).unwrap();
+    drop(tx);
+    loop {
+        let _n: i32 = recv_break!(rx);
+    }
+    let mut inputs = vec!["x", "1"].into_iter();
+    let _: Result<i32, ()> = loop {
+        break_ok_or_continue!(inputs.next().unwrap().parse::<i32>());
+    };
+    let _: Result<i32, ()> = loop {
+        unwrap_break_ok!(Some(1));
+    };
+    let _: Result<(), String> = loop {
+        let _n: i32 = unwrap_break_with_msg!(None, "missing");
+    };
+    assert_eq!(
+        since(before),
+        UnwrapStats {
+            // if, until, loc, at, catch, await, dbg, err_eprintln, flat, cf, retry and
+            // break_ok_or_continue
+            continues: 12,
+            // until, break_dbg, break_eprintln, retry, next, recv_break, break_ok_or_continue and
+            // break_ok
+            breaks: 8,
+            // with_msg
+            err_breaks: 1,
+        }
+    );
+}
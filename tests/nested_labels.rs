@@ -0,0 +1,1363 @@
+//! Every macro that takes a label, used in the innermost of three nested loops to continue or
+//! break each of them.
+
+// Each nest declares all three labels, but only uses one.
+#![allow(unused_labels)]
+
+use loop_unwrap::*;
+use std::time::{Duration, Instant};
+
+/// Runs three nested loops, `$a` (2 iterations) around `$b` (2) around `$c` (3), with `$step`
+/// in the innermost body. `$input` is None when `c == 1`, and `Some(c)` otherwise. Returns
+/// the loop bodies that ran to the end, as "abc" for `$c`, "ab" for `$b` and "a" for `$a`.
+macro_rules! nest {
+    ($a:lifetime, $b:lifetime, $c:lifetime, |$input:ident| $step:block) => {{
+        let mut trace = Vec::new();
+        $a: for a in 0..2 {
+            $b: for b in 0..2 {
+                $c: for c in 0..3 {
+                    let $input = if c == 1 { None } else { Some(c) };
+                    $step
+                    trace.push(format!("{}{}{}", a, b, c));
+                }
+                trace.push(format!("{}{}", a, b));
+            }
+            trace.push(format!("{}", a));
+        }
+        trace
+    }};
+}
+
+fn expected_continue() -> [Vec<String>; 3] {
+    [
+        nest!('a, 'b, 'c, |input| {
+            if input.is_none() {
+                continue 'a;
+            }
+        }),
+        nest!('a, 'b, 'c, |input| {
+            if input.is_none() {
+                continue 'b;
+            }
+        }),
+        nest!('a, 'b, 'c, |input| {
+            if input.is_none() {
+                continue 'c;
+            }
+        }),
+    ]
+}
+
+fn expected_break() -> [Vec<String>; 3] {
+    [
+        nest!('a, 'b, 'c, |input| {
+            if input.is_none() {
+                break 'a;
+            }
+        }),
+        nest!('a, 'b, 'c, |input| {
+            if input.is_none() {
+                break 'b;
+            }
+        }),
+        nest!('a, 'b, 'c, |input| {
+            if input.is_none() {
+                break 'c;
+            }
+        }),
+    ]
+}
+
+#[test]
+fn unwrap_continue() {
+    let expected = expected_continue();
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, 'c);
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, 'a, "message");
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, 'b, "message");
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, 'c, "message");
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, "message", 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, "message", 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, "message", 'c);
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, 'a, "format {}", 1);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, 'b, "format {}", 1);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, 'c, "format {}", 1);
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, 'a, |_| "closure");
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, 'b, |_| "closure");
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, 'c, |_| "closure");
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, 'a, || "lazy");
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, 'b, || "lazy");
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, 'c, || "lazy");
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, 'a, else |_| {});
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, 'b, else |_| {});
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue!(input, 'c, else |_| {});
+        }),
+        expected[2]
+    );
+}
+
+#[test]
+fn unwrap_continue_err() {
+    let expected = expected_continue();
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err!(input.ok_or(()), 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err!(input.ok_or(()), 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err!(input.ok_or(()), 'c);
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err!(input.ok_or(()), 'a, "message");
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err!(input.ok_or(()), 'b, "message");
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err!(input.ok_or(()), 'c, "message");
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err!(input.ok_or(()), "message", 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err!(input.ok_or(()), "message", 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err!(input.ok_or(()), "message", 'c);
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err!(input.ok_or(()), 'a, "format {}", 1);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err!(input.ok_or(()), 'b, "format {}", 1);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err!(input.ok_or(()), 'c, "format {}", 1);
+        }),
+        expected[2]
+    );
+}
+
+#[test]
+fn unwrap_continue_if() {
+    let expected = expected_continue();
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_if!(input, |_| true, 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_if!(input, |_| true, 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_if!(input, |_| true, 'c);
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_if!(input, |_| true, 'a, "message");
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_if!(input, |_| true, 'b, "message");
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_if!(input, |_| true, 'c, "message");
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_if!(input, |_| true, "message", 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_if!(input, |_| true, "message", 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_if!(input, |_| true, "message", 'c);
+        }),
+        expected[2]
+    );
+}
+
+#[test]
+fn unwrap_push_continue() {
+    let mut pushed = Vec::new();
+    let expected = expected_continue();
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_push_continue!(input, pushed, 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_push_continue!(input, pushed, 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_push_continue!(input, pushed, 'c);
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_push_continue!(input, pushed, 'a, "message");
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_push_continue!(input, pushed, 'b, "message");
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_push_continue!(input, pushed, 'c, "message");
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_push_continue!(input, pushed, "message", 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_push_continue!(input, pushed, "message", 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_push_continue!(input, pushed, "message", 'c);
+        }),
+        expected[2]
+    );
+    assert!(!pushed.is_empty());
+}
+
+#[test]
+fn unwrap_retry() {
+    let mut retries;
+    let expected = expected_continue();
+    retries = 0;
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_retry!(input, retries, 100, 'a);
+        }),
+        expected[0]
+    );
+    retries = 0;
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_retry!(input, retries, 100, 'b);
+        }),
+        expected[1]
+    );
+    retries = 0;
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_retry!(input, retries, 100, 'c);
+        }),
+        expected[2]
+    );
+    retries = 0;
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_retry!(input, retries, 100, 'a, "message");
+        }),
+        expected[0]
+    );
+    retries = 0;
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_retry!(input, retries, 100, 'b, "message");
+        }),
+        expected[1]
+    );
+    retries = 0;
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_retry!(input, retries, 100, 'c, "message");
+        }),
+        expected[2]
+    );
+    retries = 0;
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_retry!(input, retries, 100, "message", 'a);
+        }),
+        expected[0]
+    );
+    retries = 0;
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_retry!(input, retries, 100, "message", 'b);
+        }),
+        expected[1]
+    );
+    retries = 0;
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_retry!(input, retries, 100, "message", 'c);
+        }),
+        expected[2]
+    );
+}
+
+#[test]
+fn unwrap_continue_flat() {
+    let expected = expected_continue();
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_flat!(input.map(Some), 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_flat!(input.map(Some), 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_flat!(input.map(Some), 'c);
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_flat!(input.map(Some), 'a, "message");
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_flat!(input.map(Some), 'b, "message");
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_flat!(input.map(Some), 'c, "message");
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_flat!(input.map(Some), "message", 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_flat!(input.map(Some), "message", 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_flat!(input.map(Some), "message", 'c);
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_flat!(input.map(Some), 'a, "format {}", 1);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_flat!(input.map(Some), 'b, "format {}", 1);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_flat!(input.map(Some), 'c, "format {}", 1);
+        }),
+        expected[2]
+    );
+}
+
+#[test]
+fn unwrap_continue_strict() {
+    let expected = expected_continue();
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_strict!(input, 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_strict!(input, 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_strict!(input, 'c);
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_strict!(input, 'a, "message");
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_strict!(input, 'b, "message");
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_strict!(input, 'c, "message");
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_strict!(input, "message", 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_strict!(input, "message", 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_strict!(input, "message", 'c);
+        }),
+        expected[2]
+    );
+}
+
+#[test]
+fn unwrap_continue_loc() {
+    let expected = expected_continue();
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_loc!(input, 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_loc!(input, 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_loc!(input, 'c);
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_loc!(input, 'a, "message");
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_loc!(input, 'b, "message");
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_loc!(input, 'c, "message");
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_loc!(input, "message", 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_loc!(input, "message", 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_loc!(input, "message", 'c);
+        }),
+        expected[2]
+    );
+}
+
+#[test]
+fn unwrap_continue_dbg() {
+    let expected = expected_continue();
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_dbg!(input, 'a, "message");
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_dbg!(input, 'b, "message");
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_dbg!(input, 'c, "message");
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_dbg!(input, "message", 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_dbg!(input, "message", 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_dbg!(input, "message", 'c);
+        }),
+        expected[2]
+    );
+}
+
+#[test]
+fn unwrap_continue_err_eprintln() {
+    let expected = expected_continue();
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err_eprintln!(input.ok_or(()), 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err_eprintln!(input.ok_or(()), 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err_eprintln!(input.ok_or(()), 'c);
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err_eprintln!(input.ok_or(()), 'a, "message");
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err_eprintln!(input.ok_or(()), 'b, "message");
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err_eprintln!(input.ok_or(()), 'c, "message");
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err_eprintln!(input.ok_or(()), "message", 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err_eprintln!(input.ok_or(()), "message", 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_continue_err_eprintln!(input.ok_or(()), "message", 'c);
+        }),
+        expected[2]
+    );
+}
+
+#[test]
+fn unwrap_break_timeout() {
+    let later = Instant::now() + Duration::from_secs(3600);
+    let expected = expected_continue();
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_timeout!(input, 'a, deadline: later);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_timeout!(input, 'b, deadline: later);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_timeout!(input, 'c, deadline: later);
+        }),
+        expected[2]
+    );
+}
+
+#[test]
+fn unwrap_break() {
+    let expected = expected_break();
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, 'c);
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, 'a, "message");
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, 'b, "message");
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, 'c, "message");
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, "message", 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, "message", 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, "message", 'c);
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, 'a, "format {}", 1);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, 'b, "format {}", 1);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, 'c, "format {}", 1);
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, 'a, || "lazy");
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, 'b, || "lazy");
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, 'c, || "lazy");
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, 'a, summary: || "done");
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, 'a, on_err: {});
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, 'b, summary: "done");
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, 'b, on_err: {});
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, 'c, summary: || "done");
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break!(input, 'c, on_err: {});
+        }),
+        expected[2]
+    );
+}
+
+#[test]
+fn unwrap_break_dbg() {
+    let expected = expected_break();
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_dbg!(input, 'a, "message");
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_dbg!(input, 'b, "message");
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_dbg!(input, 'c, "message");
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_dbg!(input, "message", 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_dbg!(input, "message", 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_dbg!(input, "message", 'c);
+        }),
+        expected[2]
+    );
+}
+
+#[test]
+fn unwrap_break_eprintln() {
+    let expected = expected_break();
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_eprintln!(input, 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_eprintln!(input, 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_eprintln!(input, 'c);
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_eprintln!(input, 'a, "message");
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_eprintln!(input, 'b, "message");
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_eprintln!(input, 'c, "message");
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_eprintln!(input, "message", 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_eprintln!(input, "message", 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_eprintln!(input, "message", 'c);
+        }),
+        expected[2]
+    );
+}
+
+#[test]
+fn unwrap_next() {
+    let expected = expected_break();
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_next!(input.into_iter(), 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_next!(input.into_iter(), 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_next!(input.into_iter(), 'c);
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_next!(input.into_iter(), 'a, "message");
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_next!(input.into_iter(), 'b, "message");
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_next!(input.into_iter(), 'c, "message");
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_next!(input.into_iter(), "message", 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_next!(input.into_iter(), "message", 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_next!(input.into_iter(), "message", 'c);
+        }),
+        expected[2]
+    );
+}
+
+#[test]
+fn unwrap_retry_exhausted() {
+    let mut retries;
+    let expected = expected_break();
+    retries = 0;
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_retry!(input, retries, 1, 'a);
+        }),
+        expected[0]
+    );
+    retries = 0;
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_retry!(input, retries, 1, 'b);
+        }),
+        expected[1]
+    );
+    retries = 0;
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_retry!(input, retries, 1, 'c);
+        }),
+        expected[2]
+    );
+    retries = 0;
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_retry!(input, retries, 1, 'a, "message");
+        }),
+        expected[0]
+    );
+    retries = 0;
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_retry!(input, retries, 1, 'b, "message");
+        }),
+        expected[1]
+    );
+    retries = 0;
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_retry!(input, retries, 1, 'c, "message");
+        }),
+        expected[2]
+    );
+}
+
+#[test]
+fn unwrap_break_timeout_expired() {
+    let later = Instant::now() + Duration::from_secs(3600);
+    let earlier = Instant::now();
+    let expected = expected_break();
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_timeout!(input, 'a, deadline: if input.is_none() { earlier } else { later });
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_timeout!(input, 'b, deadline: if input.is_none() { earlier } else { later });
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_timeout!(input, 'c, deadline: if input.is_none() { earlier } else { later });
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_timeout!(input, 'a, deadline: if input.is_none() { earlier } else { later }, "message");
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_timeout!(input, 'b, deadline: if input.is_none() { earlier } else { later }, "message");
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_timeout!(input, 'c, deadline: if input.is_none() { earlier } else { later }, "message");
+        }),
+        expected[2]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_timeout!(input, deadline: if input.is_none() { earlier } else { later }, "message", 'a);
+        }),
+        expected[0]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_timeout!(input, deadline: if input.is_none() { earlier } else { later }, "message", 'b);
+        }),
+        expected[1]
+    );
+    assert_eq!(
+        nest!('a, 'b, 'c, |input| {
+            unwrap_break_timeout!(input, deadline: if input.is_none() { earlier } else { later }, "message", 'c);
+        }),
+        expected[2]
+    );
+}
+
+/// Runs three nested `loop`s, `$a` around `$b` around `$c`, with `$step` in the innermost body,
+/// given a `$input` that is always None. `$step` has to break one of them with a value of type
+/// `$ty`, which the loops around it break with in turn. Returns that value, and the labels of
+/// the loops that got it from the loop inside them.
+macro_rules! loop_nest {
+    ($a:lifetime, $b:lifetime, $c:lifetime, $ty:ty, |$input:ident| $step:block) => {{
+        // the code after a loop that is never broken out of is unreachable
+        #[allow(unreachable_code, unused_mut, unused_variables)]
+        let nest = {
+            let mut trace = Vec::new();
+            let value: $ty = $a: loop {
+                let value: $ty = $b: loop {
+                    let value: $ty = $c: loop {
+                        let $input = None::<i32>;
+                        $step
+                    };
+                    trace.push("c");
+                    break value;
+                };
+                trace.push("b");
+                break value;
+            };
+            (value, trace)
+        };
+        nest
+    }};
+}
+
+#[test]
+fn unwrap_break_err() {
+    type R = Result<i32, i32>;
+    let err = |to, trace| (Err(to), trace);
+    assert_eq!(
+        loop_nest!('a, 'b, 'c, R, |input| {
+            unwrap_break_err!(input.ok_or(1), 'a);
+        }),
+        err(1, vec![])
+    );
+    assert_eq!(
+        loop_nest!('a, 'b, 'c, R, |input| {
+            unwrap_break_err!(input.ok_or(2), 'b, "message");
+        }),
+        err(2, vec!["b"])
+    );
+    assert_eq!(
+        loop_nest!('a, 'b, 'c, R, |input| {
+            unwrap_break_err!(input.ok_or(3), "message", 'c);
+        }),
+        err(3, vec!["c", "b"])
+    );
+    assert_eq!(
+        loop_nest!('a, 'b, 'c, R, |input| {
+            unwrap_break_err!(input, 'b, err: 4);
+        }),
+        err(4, vec!["b"])
+    );
+    assert_eq!(
+        loop_nest!('a, 'b, 'c, R, |input| {
+            unwrap_break_err!(input.ok_or("5"), 'a, map: |_| 5);
+        }),
+        err(5, vec![])
+    );
+}
+
+#[test]
+fn unwrap_break_with_value() {
+    assert_eq!(
+        loop_nest!('a, 'b, 'c, i32, |input| {
+            unwrap_break!(input, 'a, with: 1);
+        }),
+        (1, vec![])
+    );
+    assert_eq!(
+        loop_nest!('a, 'b, 'c, i32, |input| {
+            unwrap_break!(input, with: 2, 'b);
+        }),
+        (2, vec!["b"])
+    );
+    assert_eq!(
+        loop_nest!('a, 'b, 'c, i32, |input| {
+            unwrap_break!(input, 'c, when: |_| false, with: 3);
+        }),
+        (3, vec!["c", "b"])
+    );
+    assert_eq!(
+        loop_nest!('a, 'b, 'c, Option<i32>, |input| {
+            unwrap_break!(input, 'b, when: |_| false);
+        }),
+        (None, vec!["b"])
+    );
+    assert_eq!(
+        loop_nest!('a, 'b, 'c, i32, |input| {
+            unwrap_break_or!(input, 4, 'a);
+        }),
+        (4, vec![])
+    );
+    assert_eq!(
+        loop_nest!('a, 'b, 'c, i32, |input| {
+            unwrap_break_or!(input, 5, 'c, "message");
+        }),
+        (5, vec!["c", "b"])
+    );
+}
+
+#[test]
+fn unwrap_break_ok() {
+    type R = Result<i32, ()>;
+    assert_eq!(
+        loop_nest!('a, 'b, 'c, R, |input| {
+            unwrap_break_ok!(input.or(Some(1)), 'a);
+        }),
+        (Ok(1), vec![])
+    );
+    assert_eq!(
+        loop_nest!('a, 'b, 'c, R, |input| {
+            unwrap_break_ok!(input.or(Some(2)), 'b);
+        }),
+        (Ok(2), vec!["b"])
+    );
+    assert_eq!(
+        loop_nest!('a, 'b, 'c, R, |input| {
+            unwrap_break_ok!(input, 'a);
+            unwrap_break_ok!(input.or(Some(3)), 'c);
+        }),
+        (Ok(3), vec!["c", "b"])
+    );
+}
+
+struct Guard<'a>(&'a str, &'a std::cell::RefCell<Vec<String>>);
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        self.1.borrow_mut().push(format!("drop {}", self.0));
+    }
+}
+
+#[test]
+fn breaking_the_outer_loop_skips_the_middle_one() {
+    let trace = std::cell::RefCell::new(Vec::new());
+    let log = |line: String| trace.borrow_mut().push(line);
+    'outer: for a in 0..2 {
+        let _outer = Guard("outer", &trace);
+        for b in 0..2 {
+            let _middle = Guard("middle", &trace);
+            for c in 0..2 {
+                let _inner = Guard("inner", &trace);
+                let input = if (a, b, c) == (0, 1, 1) {
+                    None
+                } else {
+                    Some(c)
+                };
+                unwrap_break!(input, 'outer);
+                log(format!("inner {}{}{}", a, b, c));
+            }
+            log(format!("middle cleanup {}{}", a, b));
+        }
+        log(format!("outer cleanup {}", a));
+    }
+    log("after".to_string());
+    assert_eq!(
+        trace.into_inner(),
+        vec![
+            "inner 000",
+            "drop inner",
+            "inner 001",
+            "drop inner",
+            "middle cleanup 00",
+            "drop middle",
+            "inner 010",
+            "drop inner",
+            // the failure at 011: no more lines from any level, but every guard is dropped
+            "drop inner",
+            "drop middle",
+            "drop outer",
+            "after",
+        ]
+    );
+}
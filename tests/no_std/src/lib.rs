@@ -0,0 +1,94 @@
+//! Builds `loop_unwrap` into a `#![no_std]` crate, using only the forms that don't print.
+//! Check it with `cargo build -p loop_unwrap_no_std`, which leaves the `std` feature off.
+
+#![no_std]
+
+use loop_unwrap::{
+    unwrap_break, unwrap_break_err, unwrap_continue, unwrap_continue_classified,
+    unwrap_continue_err, unwrap_continue_nonempty, ToOption,
+};
+
+pub fn sum_some(values: &[Option<u32>]) -> u32 {
+    let mut sum = 0;
+    for value in values {
+        sum += unwrap_continue!(*value);
+    }
+    sum
+}
+
+pub fn sum_until_none(values: &[Option<u32>]) -> u32 {
+    let mut sum = 0;
+    for value in values {
+        sum += unwrap_break!(*value);
+    }
+    sum
+}
+
+pub fn sum_ok_rows(rows: &[&[Result<u32, ()>]]) -> u32 {
+    let mut sum = 0;
+    'rows: for row in rows {
+        let mut row_sum = 0;
+        for value in row.iter() {
+            row_sum += unwrap_continue_err!(*value, 'rows);
+        }
+        sum += row_sum;
+    }
+    sum
+}
+
+pub fn first_err(values: &[Result<u32, u8>]) -> Result<u32, u8> {
+    let mut values = values.iter();
+    let mut sum = 0;
+    'outer: loop {
+        match values.next() {
+            Some(value) => sum += unwrap_break_err!(*value, 'outer),
+            None => break Ok(sum),
+        }
+    }
+}
+
+pub fn to_option(value: Result<u32, ()>) -> Option<u32> {
+    value.to_option()
+}
+
+pub fn count_pending(polls: &[core::task::Poll<u32>]) -> usize {
+    let mut pending = 0;
+    for poll in polls {
+        pending += 1;
+        unwrap_continue!(*poll);
+        pending -= 1;
+    }
+    pending
+}
+
+pub fn sum_some_logged(values: &[Option<u32>], log: &mut impl core::fmt::Write) -> u32 {
+    let mut sum = 0;
+    for (i, value) in values.iter().enumerate() {
+        sum += unwrap_continue!(*value, buf: log, "skipped {};", i);
+    }
+    sum
+}
+
+pub struct Malformed(pub bool);
+
+impl loop_unwrap::SkipError for Malformed {
+    fn is_skip(&self) -> bool {
+        self.0
+    }
+}
+
+pub fn sum_classified(values: &[Result<u32, bool>]) -> Result<u32, Malformed> {
+    let mut sum = 0;
+    for value in values {
+        sum += unwrap_continue_classified!(value.map_err(Malformed));
+    }
+    Ok(sum)
+}
+
+pub fn first_of_nonempty(rows: &[&[u32]]) -> u32 {
+    let mut sum = 0;
+    for row in rows {
+        sum += unwrap_continue_nonempty!(*row)[0];
+    }
+    sum
+}
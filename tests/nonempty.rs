@@ -0,0 +1,44 @@
+use loop_unwrap::unwrap_continue_nonempty;
+
+#[test]
+fn empty_vecs_are_skipped() {
+    let batches: Vec<Vec<i32>> = vec![vec![1, 2], vec![], vec![3]];
+    let mut seen = Vec::new();
+    for batch in batches {
+        let batch: Vec<i32> = unwrap_continue_nonempty!(batch);
+        seen.push(batch);
+    }
+    assert_eq!(seen, vec![vec![1, 2], vec![3]]);
+}
+
+#[test]
+fn empty_strings_are_skipped() {
+    let mut seen = Vec::new();
+    for name in [String::new(), "alice".to_string(), String::new()] {
+        seen.push(unwrap_continue_nonempty!(name, "empty name"));
+    }
+    assert_eq!(seen, vec!["alice".to_string()]);
+}
+
+#[test]
+fn references_are_checked_without_moving() {
+    let rows = [vec!["a"], vec![], vec!["b", "c"]];
+    let mut lengths = Vec::new();
+    for row in &rows {
+        lengths.push(unwrap_continue_nonempty!(row, "{} is empty", "row").len());
+    }
+    assert_eq!(lengths, vec![1, 2]);
+    assert_eq!(rows.len(), 3);
+}
+
+#[test]
+fn label_continues_the_outer_loop() {
+    let mut words = Vec::new();
+    'lines: for line in &["a b", "c  d", "e"] {
+        for word in line.split(' ') {
+            words.push(unwrap_continue_nonempty!(word, 'lines, "empty word"));
+        }
+        words.push("|");
+    }
+    assert_eq!(words, vec!["a", "b", "|", "c", "e", "|"]);
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "panic-in-debug")]
+
+use loop_unwrap::unwrap_continue;
+use std::panic;
+
+fn sum_valid(inputs: &[&str]) -> i32 {
+    let mut sum = 0;
+    for input in inputs {
+        sum += unwrap_continue!(input.parse::<i32>(), "Couldn't parse {:?}", input);
+    }
+    sum
+}
+
+fn count_some(values: &[Option<i32>]) -> usize {
+    let mut count = 0;
+    for value in values {
+        unwrap_continue!(*value);
+        count += 1;
+    }
+    count
+}
+
+#[test]
+fn panics_with_the_message_in_debug_builds() {
+    let result = panic::catch_unwind(|| sum_valid(&["1", "two", "3"]));
+    if cfg!(debug_assertions) {
+        let payload = result.unwrap_err();
+        assert_eq!(
+            payload.downcast_ref::<String>().unwrap(),
+            "Couldn't parse \"two\""
+        );
+    } else {
+        assert_eq!(result.unwrap(), 4);
+    }
+}
+
+#[test]
+fn panics_without_a_message_in_debug_builds() {
+    let result = panic::catch_unwind(|| count_some(&[Some(1), None, Some(3)]));
+    if cfg!(debug_assertions) {
+        assert!(result.is_err());
+    } else {
+        assert_eq!(result.unwrap(), 2);
+    }
+}
+
+#[test]
+fn forms_handling_the_failure_still_skip() {
+    let mut errors = Vec::new();
+    let mut sum = 0;
+    for input in &["1", "two", "3"] {
+        sum += unwrap_continue!(input.parse::<i32>(), collect: errors);
+    }
+    assert_eq!((sum, errors.len()), (4, 1));
+}
+
+#[test]
+fn successes_are_unaffected() {
+    assert_eq!(sum_valid(&["1", "2"]), 3);
+    assert_eq!(count_some(&[Some(1), Some(2)]), 2);
+}
@@ -0,0 +1,26 @@
+use loop_unwrap::prelude::*;
+
+#[test]
+fn prelude_brings_macros_and_traits_into_scope() {
+    let mut sum = 0;
+    let mut errors = Vec::new();
+    for input in &["1", "two", "3", "four"] {
+        let n: i32 = unwrap_continue!(input.parse(), collect: errors);
+        sum += unwrap_break!(n.checked_add(1));
+    }
+    assert_eq!(sum, 6);
+    assert_eq!(errors.len(), 2);
+
+    let mut inputs = vec!["4", "x"].into_iter();
+    let mut last = 0;
+    let stopped: Result<(), _> = loop {
+        let input = unwrap_break!(inputs.next(), with: Ok(()));
+        last = unwrap_break_err!(input.parse::<i32>());
+    };
+    assert_eq!(last, 4);
+    assert!(stopped.is_err());
+
+    assert_eq!(Some(1).to_option(), Some(1));
+    assert_eq!(ToResultLike::into_parts(None::<i32>), Err(()));
+    assert_eq!(None::<i32>.on_none(|| 7), 7);
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "quiet")]
+
+use loop_unwrap::{set_unwrap_writer, unwrap_break, unwrap_continue, unwrap_continue_err};
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn skips_without_printing() {
+    let captured = Captured::default();
+    set_unwrap_writer(Box::new(captured.clone()));
+
+    let mut sum = 0;
+    for input in &["1", "two", "3", ""] {
+        sum += unwrap_continue!(input.parse::<i32>(), "Couldn't parse {:?}", input);
+    }
+    for input in &["4", "five"] {
+        sum += unwrap_continue_err!(input.parse::<i32>(), "Couldn't parse");
+    }
+    for input in &["6", "x", "7"] {
+        sum += unwrap_break!(input.parse::<i32>(), "Stopping");
+    }
+    assert_eq!(sum, 14);
+    assert!(captured.0.lock().unwrap().is_empty());
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "std")]
+
+use loop_unwrap::recv_break;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn recv_breaks_when_the_sender_is_dropped_mid_stream() {
+    let (tx, rx) = mpsc::channel();
+    let (ack_tx, ack_rx) = mpsc::channel();
+    let sender = thread::spawn(move || {
+        for n in 0..3 {
+            tx.send(n).unwrap();
+            ack_rx.recv().unwrap();
+        }
+    });
+    let mut received = Vec::new();
+    loop {
+        received.push(recv_break!(rx));
+        let _ = ack_tx.send(());
+    }
+    sender.join().unwrap();
+    assert_eq!(received, vec![0, 1, 2]);
+}
+
+#[test]
+fn try_recv_continues_while_empty_and_breaks_on_disconnect() {
+    let (tx, rx) = mpsc::channel();
+    let mut tx = Some(tx);
+    let mut polls = 0;
+    let mut received = Vec::new();
+    'poll: loop {
+        polls += 1;
+        match polls {
+            1 | 3 => tx.as_ref().unwrap().send(polls).unwrap(),
+            5 => drop(tx.take()),
+            _ => {}
+        }
+        for _ in 0..1 {
+            received.push(recv_break!(rx.try_recv(), 'poll));
+        }
+    }
+    assert_eq!(received, vec![1, 3]);
+    assert_eq!(polls, 5);
+}
+
+#[test]
+fn recv_timeout_continues_on_timeout() {
+    let (tx, rx) = mpsc::channel();
+    let sender = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        tx.send("late").unwrap();
+    });
+    let mut timeouts = 0;
+    let mut received = Vec::new();
+    loop {
+        timeouts += 1;
+        received.push(recv_break!(rx.recv_timeout(Duration::from_millis(1))));
+        timeouts -= 1;
+    }
+    sender.join().unwrap();
+    assert_eq!(received, vec!["late"]);
+    assert!(timeouts > 1);
+}
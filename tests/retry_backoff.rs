@@ -0,0 +1,68 @@
+#![cfg(feature = "std")]
+
+use loop_unwrap::{__loop_unwrap_backoff, unwrap_retry_backoff, DEFAULT_BACKOFF_CAP};
+use std::time::{Duration, Instant};
+
+#[test]
+fn gives_up_after_max_attempts() {
+    let mut attempts = 0;
+    let mut iterations = 0;
+    let start = Instant::now();
+    loop {
+        iterations += 1;
+        let _n: i32 = unwrap_retry_backoff!(
+            "x".parse(),
+            attempts: attempts,
+            max: 5,
+            base: Duration::from_micros(1)
+        );
+        unreachable!();
+    }
+    assert_eq!((attempts, iterations), (5, 5));
+    assert!(start.elapsed() >= Duration::from_micros(1 + 2 + 4 + 8));
+}
+
+#[test]
+fn cap_limits_the_sleep_and_label_is_continued() {
+    let mut attempts = 0;
+    let mut parsed = Vec::new();
+    let start = Instant::now();
+    'outer: for _ in 0..40 {
+        for input in &["1", "x"] {
+            parsed.push(unwrap_retry_backoff!(
+                input.parse::<i32>(),
+                attempts: attempts,
+                max: 30,
+                base: Duration::from_micros(1),
+                cap: Duration::from_micros(10),
+                'outer
+            ));
+        }
+    }
+    // Without the cap, the 29 sleeps would add up to about 2^29 microseconds.
+    assert!(start.elapsed() < Duration::from_secs(60));
+    assert_eq!(attempts, 30);
+    assert_eq!(parsed, vec![1; 30]);
+}
+
+#[test]
+fn long_backoffs_stop_at_the_default_cap() {
+    let base = Duration::from_millis(1);
+    assert_eq!(
+        __loop_unwrap_backoff(base, 3, DEFAULT_BACKOFF_CAP),
+        base * 4
+    );
+    assert_eq!(
+        __loop_unwrap_backoff(base, 40, DEFAULT_BACKOFF_CAP),
+        DEFAULT_BACKOFF_CAP
+    );
+    // too large for a u32, and negative
+    assert_eq!(
+        __loop_unwrap_backoff(base, u64::MAX, DEFAULT_BACKOFF_CAP),
+        DEFAULT_BACKOFF_CAP
+    );
+    assert_eq!(
+        __loop_unwrap_backoff(base, -1, DEFAULT_BACKOFF_CAP),
+        DEFAULT_BACKOFF_CAP
+    );
+}
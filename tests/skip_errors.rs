@@ -0,0 +1,36 @@
+use loop_unwrap::SkipExt;
+
+#[test]
+fn skip_errors_yields_only_values() {
+    let mixed: Vec<Result<i32, &str>> = vec![Ok(1), Err("a"), Ok(2), Err("b"), Ok(3)];
+    let values: Vec<i32> = mixed.into_iter().skip_errors().collect();
+    assert_eq!(values, vec![1, 2, 3]);
+
+    let options = vec![None, Some('x'), None, Some('y')];
+    let values: String = options.into_iter().skip_errors().collect();
+    assert_eq!(values, "xy");
+
+    let flags = [true, false, true];
+    assert_eq!(flags.iter().copied().skip_errors().count(), 2);
+}
+
+#[test]
+fn skip_errors_with_calls_back_on_each_skip() {
+    let mixed: Vec<Result<i32, &str>> = vec![Err("a"), Ok(1), Err("b"), Err("c"), Ok(2)];
+    let mut errors = Vec::new();
+    let values: Vec<i32> = mixed
+        .into_iter()
+        .skip_errors_with(|e| errors.push(e))
+        .collect();
+    assert_eq!(values, vec![1, 2]);
+    assert_eq!(errors, vec!["a", "b", "c"]);
+
+    let mut skipped = 0;
+    let mut iter = vec![Some(1), None, None]
+        .into_iter()
+        .skip_errors_with(|()| skipped += 1);
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next(), None);
+    drop(iter);
+    assert_eq!(skipped, 2);
+}
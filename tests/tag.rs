@@ -0,0 +1,50 @@
+#![cfg(all(feature = "std", not(feature = "quiet")))]
+
+use loop_unwrap::{set_unwrap_writer, take_unwrap_writer, unwrap_continue};
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn message_starts_with_tag() {
+    let captured = Captured::default();
+    set_unwrap_writer(Box::new(captured.clone()));
+
+    let mut sum = 0;
+    for input in &["1", "two"] {
+        sum += unwrap_continue!(input.parse::<i32>(), tag: "NET", "connection failed");
+    }
+    'outer: for input in &["x"] {
+        for _ in 0..2 {
+            let n: i32 = unwrap_continue!(input.parse(), 'outer, tag: "DB", "bad row {:?}", input);
+            sum += n;
+        }
+    }
+    for _ in 0..1 {
+        unwrap_continue!(None::<()>, tag: "IO", String::from("no data"));
+    }
+    'rows: for _ in 0..1 {
+        for _ in 0..2 {
+            unwrap_continue!(None::<()>, tag: "DB", "row skipped", 'rows);
+        }
+    }
+    take_unwrap_writer();
+
+    let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+    assert_eq!(sum, 1);
+    assert_eq!(
+        output,
+        "[NET] connection failed\n[DB] bad row \"x\"\n[IO] no data\n[DB] row skipped\n"
+    );
+}
@@ -0,0 +1,100 @@
+use loop_unwrap::{unwrap_continue, AsOption, ToOption};
+use std::sync::{mpsc, Arc, Mutex};
+use std::task::Poll;
+use std::thread;
+
+#[test]
+fn to_option_on_option_and_result() {
+    assert_eq!(Some(1).to_option(), Some(1));
+    assert_eq!(None::<i32>.to_option(), None);
+    assert_eq!(Ok::<i32, ()>(2).to_option(), Some(2));
+    assert_eq!(Err::<i32, &str>("no").to_option(), None);
+
+    let name = Some(String::from("a"));
+    assert_eq!((&name).to_option(), Some(String::from("a")));
+    let parsed = "x".parse::<i32>();
+    assert_eq!((&parsed).to_option(), None);
+    assert!(parsed.is_err());
+}
+
+#[test]
+fn as_option_borrows() {
+    let name = Some(String::from("a"));
+    assert_eq!(name.as_option().map(String::as_str), Some("a"));
+    let parsed: Result<String, ()> = Err(());
+    assert_eq!(parsed.as_option(), None);
+    assert_eq!(Poll::Ready(3).as_option(), Some(&3));
+    assert_eq!(Poll::<i32>::Pending.as_option(), None);
+}
+
+#[test]
+fn try_lock_skips_while_contended() {
+    let mutex = Arc::new(Mutex::new(0));
+    let (locked_tx, locked_rx) = mpsc::channel();
+    let (release_tx, release_rx) = mpsc::channel();
+    let holder = {
+        let mutex = Arc::clone(&mutex);
+        thread::spawn(move || {
+            let mut guard = mutex.lock().unwrap();
+            *guard = 1;
+            locked_tx.send(()).unwrap();
+            release_rx.recv().unwrap();
+        })
+    };
+    locked_rx.recv().unwrap();
+
+    let mut attempts = 0;
+    let value = loop {
+        attempts += 1;
+        if attempts == 2 {
+            release_tx.send(()).unwrap();
+        }
+        let guard = unwrap_continue!(mutex.try_lock());
+        break *guard;
+    };
+    holder.join().unwrap();
+    assert_eq!(value, 1);
+    assert!(attempts >= 2);
+}
+
+#[test]
+fn try_lock_skips_poisoned_locks() {
+    let mutex = Arc::new(Mutex::new(0));
+    let poisoner = Arc::clone(&mutex);
+    let _ = thread::spawn(move || {
+        let _guard = poisoner.lock().unwrap();
+        panic!("poisoning the lock");
+    })
+    .join();
+
+    let mut locked = 0;
+    for _ in 0..3 {
+        let _guard = unwrap_continue!(mutex.try_lock());
+        locked += 1;
+    }
+    assert_eq!(locked, 0);
+    assert!(mutex.is_poisoned());
+}
+
+#[test]
+fn unit_values_flow_through_as_statements() {
+    let mut written = Vec::new();
+    let mut write = |n: i32| -> Result<(), String> {
+        if n % 2 == 0 {
+            written.push(n);
+            Ok(())
+        } else {
+            Err(format!("{} is odd", n))
+        }
+    };
+    let mut succeeded = 0;
+    for n in 0..5 {
+        unwrap_continue!(write(n));
+        unwrap_continue!(Some(()));
+        unwrap_continue!(());
+        succeeded += 1;
+    }
+    assert_eq!(succeeded, 3);
+    assert_eq!(written, vec![0, 2, 4]);
+    assert_eq!(().to_option(), Some(()));
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "tracing")]
+
+use loop_unwrap::{unwrap_break_err, unwrap_continue, unwrap_continue_err};
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+#[derive(Debug, Default, PartialEq)]
+struct Recorded {
+    level: Option<Level>,
+    target: String,
+    message: String,
+    error: Option<String>,
+}
+
+impl Visit for Recorded {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        match field.name() {
+            "message" => self.message = format!("{:?}", value),
+            "error" => self.error = Some(format!("{:?}", value)),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct CapturingSubscriber {
+    events: Arc<Mutex<Vec<Recorded>>>,
+}
+
+impl Subscriber for CapturingSubscriber {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Attributes) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event) {
+        let mut recorded = Recorded {
+            level: Some(*event.metadata().level()),
+            target: event.metadata().target().to_string(),
+            ..Recorded::default()
+        };
+        event.record(&mut recorded);
+        self.events.lock().unwrap().push(recorded);
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[derive(Debug)]
+struct DebugError;
+
+struct OpaqueError;
+
+#[test]
+fn messages_are_warn_events() {
+    let subscriber = CapturingSubscriber::default();
+    tracing::subscriber::with_default(subscriber.clone(), || {
+        for input in &["1", "two"] {
+            let _n: i32 = unwrap_continue!(input.parse(), "continue: {}", input);
+        }
+        for input in &["1", "two"] {
+            let _n = unwrap_continue_err!(input.parse::<i32>(), "continue_err");
+        }
+        let result: Result<(), _> = loop {
+            unwrap_break_err!(Err(DebugError), "break_err");
+        };
+        assert!(result.is_err());
+        let result: Result<(), OpaqueError> = loop {
+            unwrap_break_err!(Err(OpaqueError), "opaque");
+        };
+        assert!(result.is_err());
+    });
+
+    let events = subscriber.events.lock().unwrap();
+    let expected = [
+        ("continue: two", None),
+        ("continue_err", Some("ParseIntError { kind: InvalidDigit }")),
+        ("break_err", Some("DebugError")),
+        ("opaque", None),
+    ];
+    assert_eq!(events.len(), expected.len());
+    for (event, (message, error)) in events.iter().zip(&expected) {
+        assert_eq!(event.level, Some(Level::WARN));
+        assert_eq!(event.target, "loop_unwrap");
+        assert_eq!(event.message, *message);
+        assert_eq!(event.error.as_deref(), *error);
+    }
+}
@@ -0,0 +1,7 @@
+use loop_unwrap::unwrap_break_err;
+
+fn main() {
+    for input in &["1", "x"] {
+        let _n = unwrap_break_err!(input.parse::<i32>());
+    }
+}
@@ -0,0 +1,8 @@
+use loop_unwrap::unwrap_break_err;
+
+fn main() {
+    let _result: Result<i32, ()> = loop {
+        let n = unwrap_break_err!(Some(1));
+        break Ok(n);
+    };
+}
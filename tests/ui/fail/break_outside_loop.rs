@@ -0,0 +1,5 @@
+use loop_unwrap::unwrap_break;
+
+fn main() {
+    let _n: i32 = unwrap_break!("1".parse(), "message");
+}
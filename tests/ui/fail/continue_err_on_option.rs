@@ -0,0 +1,7 @@
+use loop_unwrap::unwrap_continue_err;
+
+fn main() {
+    for value in &[Some(1), None] {
+        let _n = unwrap_continue_err!(*value);
+    }
+}
@@ -0,0 +1,5 @@
+use loop_unwrap::unwrap_continue;
+
+fn main() {
+    let _n: i32 = unwrap_continue!("1".parse());
+}
@@ -0,0 +1,7 @@
+use loop_unwrap::unwrap_continue_some;
+
+fn main() {
+    for input in &["1", "two"] {
+        let _n: i32 = unwrap_continue_some!(input.parse::<i32>());
+    }
+}
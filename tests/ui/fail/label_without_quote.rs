@@ -0,0 +1,8 @@
+use loop_unwrap::unwrap_continue;
+
+fn main() {
+    'main: loop {
+        let _n: i32 = unwrap_continue!("1".parse(), "message", main);
+        break 'main;
+    }
+}
@@ -0,0 +1,10 @@
+use loop_unwrap::{unwrap_break, unwrap_continue};
+
+fn main() {
+    let message = String::from("message");
+    'main: loop {
+        let _n: i32 = unwrap_continue!("1".parse(), message.clone(), main);
+        let _n: i32 = unwrap_break!("1".parse(), || "message", main);
+        break 'main;
+    }
+}
@@ -0,0 +1,9 @@
+use loop_unwrap::{unwrap_break, unwrap_continue};
+
+fn main() {
+    'main: loop {
+        let _n: i32 = unwrap_continue!("1".parse(), main, "message");
+        let _n: i32 = unwrap_break!("2".parse(), outer, with: (), "message");
+        break 'main;
+    }
+}
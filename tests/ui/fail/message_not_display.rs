@@ -0,0 +1,9 @@
+use loop_unwrap::unwrap_continue;
+
+struct NotDisplay;
+
+fn main() {
+    for input in &["1"] {
+        let _n: i32 = unwrap_continue!(input.parse(), NotDisplay);
+    }
+}
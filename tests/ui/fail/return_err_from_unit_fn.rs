@@ -0,0 +1,5 @@
+use loop_unwrap::unwrap_return_err;
+
+fn main() {
+    let _n = unwrap_return_err!("1".parse::<i32>());
+}
@@ -0,0 +1,7 @@
+use loop_unwrap::unwrap_break;
+
+fn main() {
+    loop {
+        let _n: i32 = unwrap_break!("1".parse(), 'main);
+    }
+}
@@ -0,0 +1,34 @@
+use loop_unwrap::{unwrap_break, unwrap_continue};
+
+fn double(n: i32) -> i32 {
+    n * 2
+}
+
+fn main() {
+    let mut values = Vec::new();
+    for x in vec![Some(1), None, Some(3)] {
+        values.push(double(unwrap_continue!(x)) + 1);
+    }
+    assert_eq!(values, vec![3, 7]);
+
+    'outer: for row in vec![vec![Some(1), None], vec![Some(2)]] {
+        for x in row {
+            let n = unwrap_continue!(x, 'outer) * 10;
+            values.push(n);
+        }
+    }
+    assert_eq!(values, vec![3, 7, 10, 20]);
+
+    let mut total = 0;
+    for x in vec![Ok::<i32, ()>(1), Ok(2), Err(()), Ok(4)] {
+        total += if unwrap_break!(x) > 1 { 10 } else { 1 };
+    }
+    assert_eq!(total, 11);
+
+    'labeled: loop {
+        loop {
+            let s: &str = unwrap_break!(None::<&str>, 'labeled).trim();
+            assert!(s.is_empty());
+        }
+    }
+}
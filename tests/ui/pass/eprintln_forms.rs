@@ -0,0 +1,14 @@
+use loop_unwrap::{unwrap_break_eprintln, unwrap_continue_err_eprintln};
+
+fn main() {
+    let mut sum = 0;
+    for input in &["1", "x", "3"] {
+        sum += unwrap_continue_err_eprintln!(input.parse::<i32>(), "format {}", input);
+    }
+    assert_eq!(sum, 4);
+
+    for input in &["1", "x", "3"] {
+        sum += unwrap_break_eprintln!(input.parse::<i32>(), "message");
+    }
+    assert_eq!(sum, 5);
+}
@@ -0,0 +1,28 @@
+use loop_unwrap::{unwrap_break, unwrap_break_err, unwrap_continue, unwrap_continue_err};
+
+const INPUTS: [&str; 3] = ["1", "x", "3"];
+
+fn main() {
+    let mut sum = 0;
+    for input in &INPUTS {
+        sum += unwrap_continue!(input.parse::<i32>());
+        sum += unwrap_continue_err!(input.parse::<i32>());
+    }
+    let mut i = 0;
+    while i < INPUTS.len() {
+        i += 1;
+        sum += unwrap_continue!(INPUTS[i - 1].parse::<i32>());
+    }
+    let mut inputs = INPUTS.iter();
+    while let Some(input) = inputs.next() {
+        sum += unwrap_break!(input.parse::<i32>());
+    }
+    assert_eq!(sum, 13);
+
+    let mut inputs = INPUTS.iter();
+    let result: Result<(), _> = loop {
+        let input = unwrap_break!(inputs.next(), with: Ok(()));
+        unwrap_break_err!(input.parse::<i32>());
+    };
+    assert!(result.is_err());
+}
@@ -0,0 +1,192 @@
+use loop_unwrap::unwrap_break_err;
+
+const INPUTS: [&str; 3] = ["1", "x", "3"];
+
+fn main() {
+    let mut i = 0;
+    let result: Result<(), _> = loop {
+        if i == INPUTS.len() {
+            break Ok(());
+        }
+        let input = INPUTS[i];
+        unwrap_break_err!(input.parse::<i32>());
+        i += 1;
+    };
+    assert_eq!(i, 1);
+    assert!(result.unwrap_err().to_string().contains("invalid digit"));
+
+    let mut i = 0;
+    let result: Result<(), _> = loop {
+        if i == INPUTS.len() {
+            break Ok(());
+        }
+        let input = INPUTS[i];
+        unwrap_break_err!(input.parse::<i32>(), "message");
+        i += 1;
+    };
+    assert_eq!(i, 1);
+    assert!(result.unwrap_err().to_string().contains("invalid digit"));
+
+    let mut i = 0;
+    let result: Result<(), _> = loop {
+        if i == INPUTS.len() {
+            break Ok(());
+        }
+        let input = INPUTS[i];
+        unwrap_break_err!(input.parse::<i32>(), "format {}", input);
+        i += 1;
+    };
+    assert_eq!(i, 1);
+    assert!(result.unwrap_err().to_string().contains("invalid digit"));
+
+    let mut i = 0;
+    let result: Result<(), _> = loop {
+        if i == INPUTS.len() {
+            break Ok(());
+        }
+        let input = INPUTS[i];
+        unwrap_break_err!(input.parse::<i32>().ok(), err: "missing");
+        i += 1;
+    };
+    assert_eq!(i, 1);
+    assert_eq!(result, Err("missing"));
+
+    let mut i = 0;
+    let result: Result<(), _> = loop {
+        if i == INPUTS.len() {
+            break Ok(());
+        }
+        let input = INPUTS[i];
+        unwrap_break_err!(input.parse::<i32>().ok(), err: "missing", "message");
+        i += 1;
+    };
+    assert_eq!(i, 1);
+    assert_eq!(result, Err("missing"));
+
+    let mut rows = 0;
+    let result: Result<(), _> = 'a: loop {
+        for input in &INPUTS {
+            unwrap_break_err!(input.parse::<i32>(), 'a);
+        }
+        rows += 1;
+        break Ok(());
+    };
+    assert_eq!(rows, 0);
+    assert!(result.unwrap_err().to_string().contains("invalid digit"));
+
+    let mut rows = 0;
+    let result: Result<(), _> = 'b: loop {
+        for input in &INPUTS {
+            unwrap_break_err!(input.parse::<i32>(), 'b, "message");
+        }
+        rows += 1;
+        break Ok(());
+    };
+    assert_eq!(rows, 0);
+    assert!(result.unwrap_err().to_string().contains("invalid digit"));
+
+    let mut rows = 0;
+    let result: Result<(), _> = 'c: loop {
+        for input in &INPUTS {
+            unwrap_break_err!(input.parse::<i32>(), "message", 'c);
+        }
+        rows += 1;
+        break Ok(());
+    };
+    assert_eq!(rows, 0);
+    assert!(result.unwrap_err().to_string().contains("invalid digit"));
+
+    let mut rows = 0;
+    let result: Result<(), _> = 'd: loop {
+        for input in &INPUTS {
+            unwrap_break_err!(input.parse::<i32>(), 'd, "format {}", input);
+        }
+        rows += 1;
+        break Ok(());
+    };
+    assert_eq!(rows, 0);
+    assert!(result.unwrap_err().to_string().contains("invalid digit"));
+
+    let mut rows = 0;
+    let result: Result<(), _> = 'e: loop {
+        for input in &INPUTS {
+            unwrap_break_err!(input.parse::<i32>().ok(), 'e, err: "missing");
+        }
+        rows += 1;
+        break Ok(());
+    };
+    assert_eq!(rows, 0);
+    assert_eq!(result, Err("missing"));
+
+    let mut rows = 0;
+    let result: Result<(), _> = 'f: loop {
+        for input in &INPUTS {
+            unwrap_break_err!(input.parse::<i32>().ok(), err: "missing", 'f);
+        }
+        rows += 1;
+        break Ok(());
+    };
+    assert_eq!(rows, 0);
+    assert_eq!(result, Err("missing"));
+
+    let mut rows = 0;
+    let result: Result<(), _> = 'g: loop {
+        for input in &INPUTS {
+            unwrap_break_err!(input.parse::<i32>().ok(), 'g, err: "missing", "message");
+        }
+        rows += 1;
+        break Ok(());
+    };
+    assert_eq!(rows, 0);
+    assert_eq!(result, Err("missing"));
+
+    let mut rows = 0;
+    let result: Result<(), _> = 'h: loop {
+        for input in &INPUTS {
+            unwrap_break_err!(input.parse::<i32>().ok(), err: "missing", "message", 'h);
+        }
+        rows += 1;
+        break Ok(());
+    };
+    assert_eq!(rows, 0);
+    assert_eq!(result, Err("missing"));
+
+    let mut i = 0;
+    let result: Result<(), _> = loop {
+        if i == INPUTS.len() {
+            break Ok(());
+        }
+        let input = INPUTS[i];
+        unwrap_break_err!(input.parse::<i32>().ok(), or_err: || "missing");
+        i += 1;
+    };
+    assert_eq!(i, 1);
+    assert_eq!(result, Err("missing"));
+
+    let mut i = 0;
+    let result: Result<(), _> = 'outer: loop {
+        loop {
+            if i == INPUTS.len() {
+                break 'outer Ok(());
+            }
+            let input = INPUTS[i];
+            unwrap_break_err!(input.parse::<i32>().ok(), 'outer, or_err: || "missing");
+            unwrap_break_err!(input.parse::<i32>().ok(), or_err: || "missing", 'outer);
+            i += 1;
+        }
+    };
+    assert_eq!(i, 1);
+    assert_eq!(result, Err("missing"));
+
+    let mut i = 0;
+    let result: Result<(), _> = loop {
+        if i == INPUTS.len() {
+            break Ok(());
+        }
+        let input = INPUTS[i];
+        unwrap_break_err!(input.parse::<i32>().ok(), err: "missing", || "lazy message");
+        i += 1;
+    };
+    assert_eq!(i, 1);
+    assert_eq!(result, Err("missing"));
+}
@@ -0,0 +1,64 @@
+use loop_unwrap::unwrap_break;
+
+const INPUTS: [&str; 3] = ["1", "x", "3"];
+
+fn main() {
+    let mut sum = 0;
+    for input in &INPUTS {
+        sum += unwrap_break!(input.parse::<i32>());
+    }
+    assert_eq!(sum, 1);
+
+    let mut sum = 0;
+    for input in &INPUTS {
+        sum += unwrap_break!(input.parse::<i32>(), "message");
+    }
+    assert_eq!(sum, 1);
+
+    let mut sum = 0;
+    for input in &INPUTS {
+        sum += unwrap_break!(input.parse::<i32>(), "format {}", input);
+    }
+    assert_eq!(sum, 1);
+
+    let mut rows = 0;
+    'a: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_break!(input.parse::<i32>(), 'a);
+        }
+        rows += 1;
+    }
+    'b: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_break!(input.parse::<i32>(), 'b, "message");
+        }
+        rows += 1;
+    }
+    'c: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_break!(input.parse::<i32>(), "message", 'c);
+        }
+        rows += 1;
+    }
+    'd: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_break!(input.parse::<i32>(), 'd, "format {}", input);
+        }
+        rows += 1;
+    }
+    assert_eq!(rows, 0);
+
+    let found = 'e: loop {
+        for input in &INPUTS {
+            unwrap_break!(input.parse::<i32>(), 'e, when: |n| *n == 1);
+        }
+    };
+    assert_eq!(found, Some(1));
+
+    let found = 'f: loop {
+        for input in &INPUTS {
+            unwrap_break!(input.parse::<i32>(), 'f, when: |n| *n == 3, with: 0);
+        }
+    };
+    assert_eq!(found, 0);
+}
@@ -0,0 +1,50 @@
+use loop_unwrap::unwrap_continue_err;
+
+const INPUTS: [&str; 3] = ["1", "x", "3"];
+
+fn main() {
+    let mut sum = 0;
+    for input in &INPUTS {
+        sum += unwrap_continue_err!(input.parse::<i32>());
+    }
+    assert_eq!(sum, 4);
+
+    let mut sum = 0;
+    for input in &INPUTS {
+        sum += unwrap_continue_err!(input.parse::<i32>(), "message");
+    }
+    assert_eq!(sum, 4);
+
+    let mut sum = 0;
+    for input in &INPUTS {
+        sum += unwrap_continue_err!(input.parse::<i32>(), "format {}", input);
+    }
+    assert_eq!(sum, 4);
+
+    let mut rows = 0;
+    'a: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue_err!(input.parse::<i32>(), 'a);
+        }
+        rows += 1;
+    }
+    'b: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue_err!(input.parse::<i32>(), 'b, "message");
+        }
+        rows += 1;
+    }
+    'c: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue_err!(input.parse::<i32>(), "message", 'c);
+        }
+        rows += 1;
+    }
+    'd: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue_err!(input.parse::<i32>(), 'd, "format {}", input);
+        }
+        rows += 1;
+    }
+    assert_eq!(rows, 0);
+}
@@ -0,0 +1,33 @@
+use loop_unwrap::unwrap_continue_if;
+
+const INPUTS: [&str; 4] = ["1", "x", "-3", "4"];
+
+fn main() {
+    let mut sum = 0;
+    for input in &INPUTS {
+        sum += unwrap_continue_if!(input.parse::<i32>(), |n| *n > 0);
+        sum += unwrap_continue_if!(input.parse::<i32>(), |n| *n > 0, "message");
+    }
+    assert_eq!(sum, 10);
+
+    let mut rows = 0;
+    'a: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue_if!(input.parse::<i32>(), |n| *n > 0, 'a);
+        }
+        rows += 1;
+    }
+    'b: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue_if!(input.parse::<i32>(), |n| *n > 0, 'b, "message");
+        }
+        rows += 1;
+    }
+    'c: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue_if!(input.parse::<i32>(), |n| *n > 0, "message", 'c);
+        }
+        rows += 1;
+    }
+    assert_eq!(rows, 0);
+}
@@ -0,0 +1,143 @@
+use loop_unwrap::unwrap_continue;
+
+const INPUTS: [&str; 3] = ["1", "x", "3"];
+
+fn main() {
+    let mut sum = 0;
+    for input in &INPUTS {
+        sum += unwrap_continue!(input.parse::<i32>());
+    }
+    assert_eq!(sum, 4);
+
+    let mut sum = 0;
+    for input in &INPUTS {
+        sum += unwrap_continue!(input.parse::<i32>(), "message");
+    }
+    assert_eq!(sum, 4);
+
+    let mut sum = 0;
+    for input in &INPUTS {
+        sum += unwrap_continue!(input.parse::<i32>(), "format {}", input);
+    }
+    assert_eq!(sum, 4);
+
+    let mut sum = 0;
+    for input in &INPUTS {
+        sum += unwrap_continue!(input.parse::<i32>(), |e| e);
+    }
+    assert_eq!(sum, 4);
+
+    let mut sum = 0;
+    let mut errors = 0;
+    for input in &INPUTS {
+        sum += unwrap_continue!(input.parse::<i32>(), else |_e| { errors += 1; });
+    }
+    assert_eq!((sum, errors), (4, 1));
+
+    let mut sum = 0;
+    let mut collected = Vec::new();
+    for input in &INPUTS {
+        sum += unwrap_continue!(input.parse::<i32>(), collect: collected);
+    }
+    assert_eq!((sum, collected.len()), (4, 1));
+
+    let mut rows = 0;
+    'a: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue!(input.parse::<i32>(), 'a);
+        }
+        rows += 1;
+    }
+    'b: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue!(input.parse::<i32>(), 'b, "message");
+        }
+        rows += 1;
+    }
+    'c: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue!(input.parse::<i32>(), "message", 'c);
+        }
+        rows += 1;
+    }
+    'd: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue!(input.parse::<i32>(), 'd, "format {}", input);
+        }
+        rows += 1;
+    }
+    'e: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue!(input.parse::<i32>(), 'e, |e| e);
+        }
+        rows += 1;
+    }
+    'f: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue!(input.parse::<i32>(), |e| e, 'f);
+        }
+        rows += 1;
+    }
+    'g: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue!(input.parse::<i32>(), 'g, else |_e| {});
+        }
+        rows += 1;
+    }
+    'h: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue!(input.parse::<i32>(), else |_e| {}, 'h);
+        }
+        rows += 1;
+    }
+    'i: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue!(input.parse::<i32>(), 'i, collect: collected);
+        }
+        rows += 1;
+    }
+    'j: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue!(input.parse::<i32>(), collect: collected, 'j);
+        }
+        rows += 1;
+    }
+    assert_eq!((rows, collected.len()), (0, 5));
+
+    let mut details = 0;
+    for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue!(input.parse::<i32>(), "prefix", |_e| details += 1);
+        }
+        rows += 1;
+    }
+    'l: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue!(input.parse::<i32>(), 'l, "prefix", |_e| details += 1);
+        }
+        rows += 1;
+    }
+    'm: for _ in 0..2 {
+        for input in &INPUTS {
+            unwrap_continue!(input.parse::<i32>(), "prefix", |_e| details += 1, 'm);
+        }
+        rows += 1;
+    }
+    assert_eq!((rows, details), (2, 6));
+
+    let mut inputs = INPUTS.iter();
+    let skipped: Result<(), _> = 'n: loop {
+        let Some(input) = inputs.next() else { break Ok(()) };
+        for _ in 0..2 {
+            unwrap_continue!(input.parse::<i32>(), 'n, skip_if: |_| true);
+        }
+    };
+    let mut inputs = INPUTS.iter();
+    let stopped: Result<(), _> = 'o: loop {
+        let Some(input) = inputs.next() else { break Ok(()) };
+        for _ in 0..2 {
+            unwrap_continue!(input.parse::<i32>(), skip_if: |_| false, 'o);
+        }
+    };
+    assert!(skipped.is_ok() && stopped.is_err());
+}
@@ -0,0 +1,17 @@
+use loop_unwrap::unwrap_default;
+
+fn main() {
+    let mut values = Vec::new();
+    for input in &["1", "x"] {
+        values.push(unwrap_default!(input.parse::<i32>()));
+        values.push(unwrap_default!(input.parse::<i32>(), -1));
+        values.push(unwrap_default!(input.parse::<i32>(), -2, "message"));
+        values.push(unwrap_default!(
+            input.parse::<i32>(),
+            -3,
+            "format {}",
+            input
+        ));
+    }
+    assert_eq!(values, vec![1, 1, 1, 1, 0, -1, -2, -3]);
+}
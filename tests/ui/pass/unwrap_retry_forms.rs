@@ -0,0 +1,27 @@
+use loop_unwrap::unwrap_retry;
+
+fn main() {
+    let mut failures = 0;
+    loop {
+        unwrap_retry!(None::<i32>, failures, 2);
+    }
+    loop {
+        unwrap_retry!(None::<i32>, failures, 4, "message");
+    }
+    'a: loop {
+        loop {
+            unwrap_retry!(None::<i32>, failures, 6, 'a);
+        }
+    }
+    'b: loop {
+        loop {
+            unwrap_retry!(None::<i32>, failures, 8, 'b, "message");
+        }
+    }
+    'c: loop {
+        loop {
+            unwrap_retry!(None::<i32>, failures, 10, "message", 'c);
+        }
+    }
+    assert_eq!(failures, 10);
+}
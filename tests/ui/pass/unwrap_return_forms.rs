@@ -0,0 +1,38 @@
+use loop_unwrap::{unwrap_return, unwrap_return_err};
+
+fn sum_or_default(inputs: &[&str]) -> i32 {
+    let mut sum = 0;
+    for input in inputs {
+        sum += unwrap_return!(input.parse::<i32>());
+    }
+    sum
+}
+
+fn sum_or(inputs: &[&str]) -> i32 {
+    let mut sum = 0;
+    for input in inputs {
+        sum += unwrap_return!(input.parse::<i32>(), -1);
+        sum += unwrap_return!(input.parse::<i32>(), -2, "message");
+        sum += unwrap_return!(input.parse::<i32>(), -3, "format {}", input);
+    }
+    sum
+}
+
+fn sum_or_err(inputs: &[&str]) -> Result<i32, Box<dyn std::error::Error>> {
+    let mut sum = 0;
+    for input in inputs {
+        sum += unwrap_return_err!(input.parse::<i32>());
+        sum += unwrap_return_err!(input.parse::<i32>(), "message");
+        sum += unwrap_return_err!(input.parse::<i32>(), "format {}", input);
+    }
+    Ok(sum)
+}
+
+fn main() {
+    assert_eq!(sum_or_default(&["1", "2"]), 3);
+    assert_eq!(sum_or_default(&["1", "x"]), 0);
+    assert_eq!(sum_or(&["1", "2"]), 9);
+    assert_eq!(sum_or(&["1", "x"]), -1);
+    assert_eq!(sum_or_err(&["1", "2"]).unwrap(), 9);
+    assert!(sum_or_err(&["1", "x"]).is_err());
+}